@@ -0,0 +1,182 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::collision::{Collidable, Collider, check_collision_wrapped, segment_intersects_rect};
+use crate::projectile::{Projectile, ProjectileType};
+use crate::visual_config::ColorConfig;
+
+/// Barriers fade out during their last second
+const BARRIER_FADE_TIME: f32 = 1.0;
+
+/// Size, lifetime and spawn rate of the barriers a Builder puts down
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BarrierConfig {
+    pub length: f32,    // Long side of the barrier
+    pub thickness: f32, // Short side of the barrier
+    pub duration: f32,  // Seconds a barrier stays up
+    pub interval: f32,  // Seconds between two barriers of the same Builder
+}
+
+impl Default for BarrierConfig {
+    fn default() -> Self {
+        Self {
+            length: 120.0,
+            thickness: 14.0,
+            duration: 4.0,
+            interval: 5.0,
+        }
+    }
+}
+
+/// Temporary wall that blocks the player and the projectiles flying into it
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Barrier {
+    #[serde(with = "crate::serde_compat::vec2")]
+    pub pos: Vec2,
+    #[serde(with = "crate::serde_compat::vec2")]
+    pub size: Vec2,
+    pub time_remaining: f32,
+    pub color: ColorConfig,
+}
+
+impl Barrier {
+    /// Barrier across the direction `facing`, so it blocks the way the Builder came from
+    pub fn across(pos: Vec2, facing: Vec2, config: &BarrierConfig, color: ColorConfig) -> Self {
+        let size = if facing.x.abs() > facing.y.abs() {
+            Vec2::new(config.thickness, config.length)
+        } else {
+            Vec2::new(config.length, config.thickness)
+        };
+        Self {
+            pos,
+            size,
+            time_remaining: config.duration,
+            color,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.time_remaining -= dt;
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.time_remaining <= 0.0
+    }
+
+    /// Energy balls, cannon shots and missiles stop at barriers, pulses spread over them
+    /// and gravity wells pull across them
+    pub fn blocks(&self, projectile: &Projectile, bounds: Option<Vec2>) -> bool {
+        if self.is_expired()
+            || matches!(
+                projectile.projectile_type,
+                ProjectileType::Pulse | ProjectileType::GravityWell
+            )
+        {
+            return false;
+        }
+        check_collision_wrapped(
+            &self.collider(),
+            self.pos,
+            &projectile.collider(),
+            projectile.position(),
+            bounds,
+        )
+        .collided
+    }
+
+    /// Whether the barrier stands between `from` and `to`
+    pub fn blocks_sight(&self, from: Vec2, to: Vec2) -> bool {
+        !self.is_expired() && segment_intersects_rect(from, to, self.pos, self.size.x, self.size.y)
+    }
+
+    pub fn draw(&self) {
+        let alpha = (self.time_remaining / BARRIER_FADE_TIME).clamp(0.0, 1.0);
+        let mut color = self.color.to_color();
+        color.a *= alpha;
+        let top_left = self.pos - self.size / 2.0;
+        draw_rectangle(top_left.x, top_left.y, self.size.x, self.size.y, color);
+        draw_rectangle_lines(
+            top_left.x,
+            top_left.y,
+            self.size.x,
+            self.size.y,
+            2.0,
+            Color::new(1.0, 1.0, 1.0, 0.6 * alpha),
+        );
+    }
+}
+
+/// Whether nothing blocks the view from `from` to `to`, a tether can't link through a barrier.
+/// Enemies don't aim yet, so no enemy fire is gated on it.
+pub fn has_line_of_sight(from: Vec2, to: Vec2, barriers: &[Barrier]) -> bool {
+    !barriers
+        .iter()
+        .any(|barrier| barrier.blocks_sight(from, to))
+}
+
+impl Collidable for Barrier {
+    fn collider(&self) -> Collider {
+        Collider::Rect {
+            width: self.size.x,
+            height: self.size.y,
+        }
+    }
+
+    fn position(&self) -> Vec2 {
+        self.pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn energy_ball_at(pos: Vec2) -> Projectile {
+        let mut projectile = Projectile::for_test(0, ProjectileType::EnergyBall, pos);
+        projectile.vel = Vec2::new(projectile.stats.speed, 0.0);
+        projectile
+    }
+
+    #[test]
+    fn test_active_barrier_stops_projectiles() {
+        // A vertical wall at x = 100
+        let mut barrier = Barrier::across(
+            Vec2::new(100.0, 0.0),
+            Vec2::X,
+            &BarrierConfig::default(),
+            ColorConfig::white(),
+        );
+
+        let mut projectile = energy_ball_at(Vec2::new(0.0, 0.0));
+        let mut stopped = false;
+        for _ in 0..30 {
+            projectile.update(1.0 / 30.0);
+            if barrier.blocks(&projectile, None) {
+                stopped = true;
+                break;
+            }
+        }
+        assert!(stopped);
+        assert!(projectile.pos.x < 100.0);
+
+        // Once it expired the barrier lets everything through
+        barrier.update(barrier.time_remaining);
+        assert!(!barrier.blocks(&projectile, None));
+    }
+
+    #[test]
+    fn test_barrier_blocks_line_of_sight() {
+        let barriers = vec![Barrier::across(
+            Vec2::new(100.0, 0.0),
+            Vec2::X,
+            &BarrierConfig::default(),
+            ColorConfig::white(),
+        )];
+        let from = Vec2::new(0.0, 0.0);
+
+        assert!(!has_line_of_sight(from, Vec2::new(200.0, 0.0), &barriers));
+        // Around the end of the wall the view is clear again
+        assert!(has_line_of_sight(from, Vec2::new(0.0, 200.0), &barriers));
+        assert!(has_line_of_sight(from, Vec2::new(200.0, 200.0), &barriers));
+    }
+}
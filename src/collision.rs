@@ -23,7 +23,6 @@ pub trait Collidable {
 #[derive(Debug, Clone, Copy)]
 pub struct CollisionData {
     pub collided: bool,
-    #[allow(dead_code)]
     pub penetration_depth: f32,
     pub normal: Vec2, // Points from object 2 to object 1
 }
@@ -79,6 +78,90 @@ pub fn check_collision(
     }
 }
 
+/// Check collision between two entities in a world whose edges wrap around at `bounds`,
+/// so entities near opposite edges still collide. Without bounds this is `check_collision`.
+pub fn check_collision_wrapped(
+    collider1: &Collider,
+    pos1: Vec2,
+    collider2: &Collider,
+    pos2: Vec2,
+    bounds: Option<Vec2>,
+) -> CollisionData {
+    match bounds {
+        Some(bounds) => {
+            // Move the first entity to its closest wrapped image next to the second one
+            let image1 = pos2 + wrapped_delta(pos1, pos2, bounds);
+            check_collision(collider1, image1, collider2, pos2)
+        }
+        None => check_collision(collider1, pos1, collider2, pos2),
+    }
+}
+
+/// Shortest vector from `to` to `from` when the world wraps around at `bounds`
+pub fn wrapped_delta(from: Vec2, to: Vec2, bounds: Vec2) -> Vec2 {
+    let mut delta = from - to;
+    if bounds.x > 0.0 {
+        delta.x -= bounds.x * (delta.x / bounds.x).round();
+    }
+    if bounds.y > 0.0 {
+        delta.y -= bounds.y * (delta.y / bounds.y).round();
+    }
+    delta
+}
+
+/// Maps a position back into `[0, bounds)` on both axes
+pub fn wrap_position(pos: Vec2, bounds: Vec2) -> Vec2 {
+    Vec2::new(
+        if bounds.x > 0.0 {
+            pos.x.rem_euclid(bounds.x)
+        } else {
+            pos.x
+        },
+        if bounds.y > 0.0 {
+            pos.y.rem_euclid(bounds.y)
+        } else {
+            pos.y
+        },
+    )
+}
+
+/// Fraction of the overlap that is resolved per logic step, correcting it all at once jitters
+const SEPARATION_FRACTION: f32 = 0.4;
+/// Overlaps are resolved at least this far per step, so they vanish instead of shrinking forever
+const MIN_SEPARATION_STEP: f32 = 0.5;
+
+/// Offset that moves object 1 out of object 2 for one logic step, object 2 moves by the negation.
+/// Each object takes half of the correction.
+pub fn separation_offset(collision: &CollisionData) -> Vec2 {
+    if !collision.collided {
+        return Vec2::ZERO;
+    }
+
+    let penetration = collision.penetration_depth;
+    let correction = (penetration * SEPARATION_FRACTION).max(penetration.min(MIN_SEPARATION_STEP));
+    collision.normal * (correction / 2.0)
+}
+
+/// Velocities after a 1D elastic collision along `normal` (pointing from object 2 to object 1)
+pub fn elastic_collision_velocities(
+    vel1: Vec2,
+    mass1: f32,
+    vel2: Vec2,
+    mass2: f32,
+    normal: Vec2,
+) -> (Vec2, Vec2) {
+    let vel_along_normal = (vel1 - vel2).dot(normal);
+    let total_mass = mass1 + mass2;
+    if total_mass <= 0.0 {
+        return (vel1, vel2);
+    }
+
+    // The lighter object takes the larger share of the velocity change
+    let new_vel1 = vel1 - normal * (2.0 * mass2 / total_mass * vel_along_normal);
+    let new_vel2 = vel2 + normal * (2.0 * mass1 / total_mass * vel_along_normal);
+    (new_vel1, new_vel2)
+}
+
 /// Check collision between two circles
 fn circle_circle(pos1: Vec2, r1: f32, pos2: Vec2, r2: f32) -> CollisionData {
     let delta = pos1 - pos2;
@@ -189,6 +272,38 @@ fn rect_rect(pos1: Vec2, w1: f32, h1: f32, pos2: Vec2, w2: f32, h2: f32) -> Coll
     }
 }
 
+/// Whether the segment from `start` to `end` passes through an axis-aligned rectangle (centered)
+pub fn segment_intersects_rect(start: Vec2, end: Vec2, pos: Vec2, width: f32, height: f32) -> bool {
+    let half = Vec2::new(width / 2.0, height / 2.0);
+    let min = pos - half;
+    let max = pos + half;
+    let dir = end - start;
+
+    // Slab test: clip the segment's parameter range [0, 1] against both axes
+    let mut t_enter = 0.0_f32;
+    let mut t_exit = 1.0_f32;
+    for (origin, delta, low, high) in [
+        (start.x, dir.x, min.x, max.x),
+        (start.y, dir.y, min.y, max.y),
+    ] {
+        if delta.abs() < f32::EPSILON {
+            // Parallel to this axis, it has to lie between the two sides
+            if origin < low || origin > high {
+                return false;
+            }
+        } else {
+            let t1 = (low - origin) / delta;
+            let t2 = (high - origin) / delta;
+            t_enter = t_enter.max(t1.min(t2));
+            t_exit = t_exit.min(t1.max(t2));
+            if t_enter > t_exit {
+                return false;
+            }
+        }
+    }
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -226,4 +341,98 @@ mod tests {
         let result = circle_rect(circle_pos, 3.0, rect_pos, 4.0, 4.0);
         assert!(result.collided);
     }
+
+    #[test]
+    fn test_separation_resolves_overlap() {
+        let circle = Collider::Circle { radius: 10.0 };
+        let mut pos1 = Vec2::new(0.0, 0.0);
+        let mut pos2 = Vec2::new(5.0, 1.0);
+
+        for _ in 0..20 {
+            let collision = check_collision(&circle, pos1, &circle, pos2);
+            let offset = separation_offset(&collision);
+            pos1 += offset;
+            pos2 -= offset;
+        }
+
+        assert!(!check_collision(&circle, pos1, &circle, pos2).collided);
+        // The overlap is never over-corrected into a gap
+        assert!(pos1.distance(pos2) < 20.0 + MIN_SEPARATION_STEP);
+    }
+
+    #[test]
+    fn test_wrapped_circle_collision_across_edge() {
+        let bounds = Vec2::new(800.0, 600.0);
+        let circle = Collider::Circle { radius: 5.0 };
+        let left = Vec2::new(2.0, 300.0);
+        let right = Vec2::new(797.0, 300.0);
+
+        // Far apart on screen, but only 5 units apart across the wrapped edge
+        assert!(!check_collision(&circle, left, &circle, right).collided);
+        let result = check_collision_wrapped(&circle, left, &circle, right, Some(bounds));
+        assert!(result.collided);
+        assert!((result.penetration_depth - 5.0).abs() < 1e-4);
+        // Normal points from the right circle over the edge towards the left one
+        assert!(result.normal.x > 0.0);
+
+        // Also across the top and bottom edge
+        let top = Vec2::new(400.0, 1.0);
+        let bottom = Vec2::new(400.0, 598.0);
+        assert!(check_collision_wrapped(&circle, top, &circle, bottom, Some(bounds)).collided);
+        assert!(!check_collision_wrapped(&circle, top, &circle, bottom, None).collided);
+
+        assert_eq!(
+            wrap_position(Vec2::new(-3.0, 605.0), bounds),
+            Vec2::new(797.0, 5.0)
+        );
+    }
+
+    #[test]
+    fn test_segment_rect_intersection() {
+        let rect_pos = Vec2::new(50.0, 0.0);
+        // Straight through, diagonally through and ending inside the rectangle
+        assert!(segment_intersects_rect(
+            Vec2::ZERO,
+            Vec2::new(100.0, 0.0),
+            rect_pos,
+            10.0,
+            40.0
+        ));
+        assert!(segment_intersects_rect(
+            Vec2::new(0.0, -30.0),
+            Vec2::new(100.0, 30.0),
+            rect_pos,
+            10.0,
+            40.0
+        ));
+        assert!(segment_intersects_rect(
+            Vec2::ZERO,
+            rect_pos,
+            rect_pos,
+            10.0,
+            40.0
+        ));
+        // Passing above, stopping short and running parallel beside it
+        assert!(!segment_intersects_rect(
+            Vec2::new(0.0, -30.0),
+            Vec2::new(100.0, -30.0),
+            rect_pos,
+            10.0,
+            40.0
+        ));
+        assert!(!segment_intersects_rect(
+            Vec2::ZERO,
+            Vec2::new(40.0, 0.0),
+            rect_pos,
+            10.0,
+            40.0
+        ));
+        assert!(!segment_intersects_rect(
+            Vec2::new(60.0, -50.0),
+            Vec2::new(60.0, 50.0),
+            rect_pos,
+            10.0,
+            40.0
+        ));
+    }
 }
@@ -0,0 +1,131 @@
+use macroquad::prelude::*;
+
+use crate::roto_script::RotoScriptManager;
+
+/// Number of scrollback lines kept in memory
+const MAX_SCROLLBACK: usize = 50;
+/// Number of scrollback lines shown above the input line
+const VISIBLE_LINES: usize = 10;
+const LINE_HEIGHT: f32 = 18.0;
+const FONT_SIZE: f32 = 18.0;
+
+/// Debug console to call Roto functions by name while the game runs, toggled with backtick
+pub struct Console {
+    pub open: bool,
+    input: String,
+    scrollback: Vec<String>,
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            input: String::new(),
+            scrollback: vec![
+                format!(
+                    "Available: {}",
+                    RotoScriptManager::console_functions()
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                "Type 'functions' to see which ones the script defines".to_owned(),
+            ],
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        // Drop the queued characters, so the backtick doesn't end up in the input line
+        while get_char_pressed().is_some() {}
+    }
+
+    /// Reads typed characters and runs the command on Enter
+    pub fn process_input(&mut self, roto_manager: &mut RotoScriptManager) {
+        while let Some(c) = get_char_pressed() {
+            if !c.is_control() && c != '`' {
+                self.input.push(c);
+            }
+        }
+
+        if is_key_pressed(KeyCode::Backspace) {
+            self.input.pop();
+        }
+
+        if is_key_pressed(KeyCode::Enter) {
+            let command = std::mem::take(&mut self.input);
+            let command = command.trim();
+            if command.is_empty() {
+                return;
+            }
+
+            self.push_line(format!("> {}", command));
+            let mut parts = command.split_whitespace();
+            let name = parts.next().unwrap_or_default();
+            let args: Vec<&str> = parts.collect();
+            if name == "functions" {
+                self.push_function_report(roto_manager);
+                return;
+            }
+            match roto_manager.call_from_console(name, &args) {
+                Ok(result) => self.push_line(result),
+                Err(err) => self.push_line(err),
+            }
+        }
+    }
+
+    /// Lists the expected Roto functions the script is missing, those use their defaults
+    fn push_function_report(&mut self, roto_manager: &mut RotoScriptManager) {
+        match roto_manager.function_report() {
+            Ok(report) => {
+                let defaulted: Vec<&str> = report
+                    .iter()
+                    .filter(|(_, found)| !found)
+                    .map(|(name, _)| *name)
+                    .collect();
+                self.push_line(format!(
+                    "Found {} of {} functions",
+                    report.len() - defaulted.len(),
+                    report.len()
+                ));
+                if !defaulted.is_empty() {
+                    self.push_line(format!("Defaulted: {}", defaulted.join(", ")));
+                }
+            }
+            Err(err) => self.push_line(err),
+        }
+    }
+
+    fn push_line(&mut self, line: String) {
+        self.scrollback.push(line);
+        if self.scrollback.len() > MAX_SCROLLBACK {
+            self.scrollback.remove(0);
+        }
+    }
+
+    pub fn draw(&self) {
+        if !self.open {
+            return;
+        }
+
+        let height = LINE_HEIGHT * (VISIBLE_LINES + 1) as f32 + 10.0;
+        let y0 = screen_height() - height;
+        draw_rectangle(
+            0.0,
+            y0,
+            screen_width(),
+            height,
+            Color::new(0.0, 0.0, 0.0, 0.8),
+        );
+
+        let first = self.scrollback.len().saturating_sub(VISIBLE_LINES);
+        for (i, line) in self.scrollback[first..].iter().enumerate() {
+            let y = y0 + LINE_HEIGHT * (i + 1) as f32;
+            draw_text(line, 10.0, y, FONT_SIZE, LIGHTGRAY);
+        }
+
+        // Blinking cursor after the input line
+        let cursor = if get_time().fract() < 0.5 { "_" } else { "" };
+        let input_line = format!("> {}{}", self.input, cursor);
+        draw_text(&input_line, 10.0, screen_height() - 10.0, FONT_SIZE, YELLOW);
+    }
+}
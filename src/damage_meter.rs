@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+
+/// Seconds of damage the sandbox DPS readout averages over
+pub const DPS_WINDOW: f32 = 5.0;
+
+/// Rolling damage per second, the sum of the damage within the last `window` seconds
+#[derive(Debug, Clone)]
+pub struct DamageMeter {
+    window: f32,
+    hits: VecDeque<(f32, f32)>, // Time of the hit and the damage it dealt, oldest first
+}
+
+impl DamageMeter {
+    pub fn new(window: f32) -> Self {
+        Self {
+            window,
+            hits: VecDeque::new(),
+        }
+    }
+
+    /// Adds `damage` dealt at `time`, hits are expected in time order
+    pub fn record(&mut self, time: f32, damage: f32) {
+        if damage > 0.0 {
+            self.hits.push_back((time, damage));
+        }
+        self.forget_before(time - self.window);
+    }
+
+    /// Damage per second over the window ending at `now`
+    pub fn dps(&self, now: f32) -> f32 {
+        if self.window <= 0.0 {
+            return 0.0;
+        }
+        let total: f32 = self
+            .hits
+            .iter()
+            .filter(|(time, _)| *time > now - self.window)
+            .map(|(_, damage)| damage)
+            .sum();
+        total / self.window
+    }
+
+    pub fn clear(&mut self) {
+        self.hits.clear();
+    }
+
+    fn forget_before(&mut self, time: f32) {
+        while self
+            .hits
+            .front()
+            .is_some_and(|(hit_time, _)| *hit_time <= time)
+        {
+            self.hits.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dps_is_the_windowed_damage_sum() {
+        let mut meter = DamageMeter::new(2.0);
+        meter.record(0.5, 10.0);
+        meter.record(1.0, 10.0);
+        meter.record(1.5, 20.0);
+        assert_eq!(meter.dps(1.5), 20.0);
+
+        // The first hit left the window, the others are still in it
+        assert_eq!(meter.dps(2.6), 15.0);
+
+        // Long after the last hit the readout drops back to zero
+        meter.record(10.0, 0.0);
+        assert_eq!(meter.dps(10.0), 0.0);
+        assert!(meter.hits.is_empty());
+    }
+}
@@ -1,89 +1,546 @@
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::collision::{Collidable, Collider};
-use crate::entity::{EntityId, EntityStats};
-use crate::visual_config::{EnemyVisualConfig, draw_direction_indicator};
+use crate::barrier::{Barrier, BarrierConfig};
+use crate::collision::{Collidable, Collider, wrap_position};
+use crate::entity::{EntityId, EntityStats, SpawnCommand, reference_ticks};
+use crate::projectile::{ProjectileStats, ProjectileType};
+use crate::visual_config::{
+    BlendConfig, ColorConfig, EnemyVisualConfig, ProjectileVisualConfig, draw_direction_indicator,
+};
+use crate::weapon::WeaponType;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Seconds the health bar stays visible after the last hit
+const HEALTH_BAR_FADE_TIME: f32 = 1.5;
+/// Seconds an enemy flashes white after taking damage
+const HIT_FLASH_TIME: f32 = 0.12;
+
+/// Seconds between teleports of a Teleporter
+const TELEPORT_COOLDOWN: f32 = 4.0;
+/// Seconds the teleport is telegraphed before the Teleporter vanishes
+const TELEPORT_TELEGRAPH_TIME: f32 = 0.8;
+/// Teleport destinations are within this distance range around the player
+const TELEPORT_MIN_DISTANCE: f32 = 120.0;
+const TELEPORT_MAX_DISTANCE: f32 = 220.0;
+
+/// Seconds a Phaser stays solid, then ethereal
+const PHASER_SOLID_TIME: f32 = 2.5;
+const PHASER_ETHEREAL_TIME: f32 = 1.5;
+/// Seconds before a phase change in which the Phaser fades towards the next phase
+const PHASER_FADE_TIME: f32 = 0.5;
+/// Opacity of an ethereal Phaser
+const PHASER_ETHEREAL_ALPHA: f32 = 0.25;
+
+/// Health of the sandbox practice dummy, enough to soak up damage for minutes
+const DUMMY_HEALTH: f32 = 1_000_000.0;
+const DUMMY_RADIUS: f32 = 24.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum EnemyType {
     Basic,
     Chaser,
+    Teleporter,
+    Berserker,
+    Phaser,
+    Builder,
+    Glass,
+    Dummy, // Sandbox practice target, never moves or hurts the player
 }
 
+/// How a Berserker speeds up when it gets close to the player
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BerserkerRamp {
+    pub start_distance: f32, // Distance to the player at which the ramp starts
+    pub max_multiplier: f32, // Speed and steering multiplier when touching the player
+}
+
+impl Default for BerserkerRamp {
+    fn default() -> Self {
+        Self {
+            start_distance: 250.0,
+            max_multiplier: 2.5,
+        }
+    }
+}
+
+impl BerserkerRamp {
+    /// Multiplier growing linearly from 1.0 at start_distance to max_multiplier at the player
+    pub fn multiplier(&self, distance: f32) -> f32 {
+        if self.start_distance <= 0.0 || distance >= self.start_distance {
+            return 1.0;
+        }
+        let closeness = 1.0 - distance / self.start_distance;
+        1.0 + (self.max_multiplier - 1.0) * closeness
+    }
+}
+
+/// Ring of shards a Glass enemy leaves behind when it is killed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ShardConfig {
+    pub count: u32,          // Shards in the ring
+    pub damage: f32,         // Damage a shard deals to the player
+    pub speed: f32,          // Pixels per second
+    pub radius: f32,         // Size of a shard
+    pub time_to_live: f32,   // Seconds until a shard vanishes
+    pub telegraph_time: f32, // Seconds the glass cracks before the shards fly
+}
+
+impl Default for ShardConfig {
+    fn default() -> Self {
+        Self {
+            count: 8,
+            damage: 5.0,
+            speed: 180.0,
+            radius: 5.0,
+            time_to_live: 1.5,
+            telegraph_time: 0.3,
+        }
+    }
+}
+
+/// Shards about to fly from where a Glass enemy died
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ShardBurst {
+    #[serde(with = "crate::serde_compat::vec2")]
+    pub pos: Vec2,
+    pub owner: EntityId, // The Glass enemy, the shards count as enemy projectiles
+    pub time_remaining: f32,
+    pub visual_config: ProjectileVisualConfig, // The shards take the colors of the glass
+}
+
+impl ShardBurst {
+    /// One projectile per shard, evenly spread around the ring
+    pub fn shards(&self, config: &ShardConfig) -> Vec<SpawnCommand> {
+        let stats = ProjectileStats {
+            damage: config.damage,
+            speed: config.speed,
+            radius: config.radius,
+            time_to_live: config.time_to_live,
+            ..ProjectileStats::energy_ball_default()
+        };
+        (0..config.count)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::TAU / config.count as f32;
+                SpawnCommand::Projectile {
+                    projectile_type: ProjectileType::EnergyBall,
+                    pos: self.pos,
+                    vel: Vec2::from_angle(angle) * config.speed,
+                    stats,
+                    // Shards fly like energy balls, they never hit enemies or count as kills
+                    weapon_type: WeaponType::EnergyBall,
+                    visual_config: Some(self.visual_config),
+                }
+            })
+            .collect()
+    }
+}
+
+/// How often chasers pick a new approach angle, so they surround the player instead of queueing up
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ChaserReassess {
+    pub interval: f32,  // Seconds between two reassessments
+    pub max_angle: f32, // Largest offset from the direct line to the player, in degrees
+}
+
+impl Default for ChaserReassess {
+    fn default() -> Self {
+        Self {
+            interval: 3.0,
+            max_angle: 40.0,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Enemy {
     pub id: EntityId,
+    #[serde(with = "crate::serde_compat::vec2")]
     pub pos: Vec2,
+    #[serde(with = "crate::serde_compat::vec2")]
+    pub prev_pos: Vec2, // Position at the start of the logic step, for render interpolation
+    #[serde(with = "crate::serde_compat::vec2")]
     pub vel: Vec2,
     pub enemy_type: EnemyType,
     pub stats: EntityStats,
     pub visual_config: EnemyVisualConfig,
+    pub health: f32,
+    pub last_damaged: f32, // Counts down from HEALTH_BAR_FADE_TIME after each hit
+    #[serde(default)]
+    pub hit_flash: f32, // Counts down from HIT_FLASH_TIME after each hit
+    pub teleport_timer: f32, // Time until the next teleport is telegraphed (Teleporter only)
+    pub telegraph_timer: f32, // Time left until the telegraphed teleport happens
+    pub slow_timer: f32,   // Time left on the slow debuff
+    pub slow_factor: f32,  // Multiplier on max_speed while slowed
+    #[serde(default)]
+    pub berserker_ramp: BerserkerRamp, // Speed-up near the player (Berserker only)
+    #[serde(default = "default_rage")]
+    pub rage: f32, // Current speed and steering multiplier from the ramp
+    #[serde(default)]
+    pub phase_timer: f32, // Time until the next phase change (Phaser only)
+    #[serde(default)]
+    pub ethereal: bool, // Ethereal enemies take no damage and pass through everything
+    #[serde(default)]
+    pub reassess: ChaserReassess,
+    #[serde(default)]
+    pub reassess_timer: f32, // Time until the next approach angle is picked
+    #[serde(default)]
+    pub approach_angle: f32, // Current approach offset from the direct line, in radians
+    #[serde(default)]
+    pub barrier: BarrierConfig, // Barriers put down by a Builder
+    #[serde(default)]
+    pub barrier_timer: f32, // Time until the next barrier (Builder only)
+    #[serde(default)]
+    pub contact_cooldown: f32, // Time until the enemy can hurt the player again (per-enemy contacts)
+    #[serde(skip)]
+    pub damage_taken: f32, // Damage since the last drain_damage_taken, practice dummies only
+}
+
+// Saves from before the Berserker have no rage, every enemy moves at its normal speed
+fn default_rage() -> f32 {
+    1.0
 }
 
 impl Enemy {
+    pub fn new(
+        id: EntityId,
+        enemy_type: EnemyType,
+        pos: Vec2,
+        vel: Vec2,
+        stats: EntityStats,
+        visual_config: EnemyVisualConfig,
+    ) -> Self {
+        Self {
+            id,
+            pos,
+            prev_pos: pos,
+            vel,
+            enemy_type,
+            stats,
+            visual_config,
+            health: stats.max_health,
+            last_damaged: 0.0,
+            hit_flash: 0.0,
+            teleport_timer: TELEPORT_COOLDOWN,
+            telegraph_timer: 0.0,
+            slow_timer: 0.0,
+            slow_factor: 1.0,
+            berserker_ramp: BerserkerRamp::default(),
+            rage: 1.0,
+            phase_timer: PHASER_SOLID_TIME,
+            ethereal: false,
+            reassess: ChaserReassess::default(),
+            reassess_timer: ChaserReassess::default().interval,
+            approach_angle: 0.0,
+            barrier: BarrierConfig::default(),
+            barrier_timer: BarrierConfig::default().interval,
+            contact_cooldown: 0.0,
+            damage_taken: 0.0,
+        }
+    }
+
+    /// Stats of the sandbox practice dummy, it isn't configured by the script
+    pub fn dummy_stats() -> EntityStats {
+        EntityStats {
+            radius: DUMMY_RADIUS,
+            max_speed: 0.0,
+            acceleration: 0.0,
+            friction: 0.0,
+            max_health: DUMMY_HEALTH,
+        }
+    }
+
     pub fn override_stats(&mut self, stats: EntityStats) {
         self.stats = stats;
+        self.health = self.health.min(stats.max_health);
+    }
+
+    pub fn take_damage(&mut self, damage: f32) {
+        if !self.is_vulnerable() {
+            return;
+        }
+        self.health -= damage;
+        // Only the practice dummies feed the sandbox DPS readout
+        if self.enemy_type == EnemyType::Dummy {
+            self.damage_taken += damage;
+        }
+        self.last_damaged = HEALTH_BAR_FADE_TIME;
+        self.hit_flash = HIT_FLASH_TIME;
+    }
+
+    /// Damage taken since the last call
+    pub fn drain_damage_taken(&mut self) -> f32 {
+        std::mem::take(&mut self.damage_taken)
+    }
+
+    /// Practice dummies don't touch the player
+    pub fn is_harmless(&self) -> bool {
+        self.enemy_type == EnemyType::Dummy
+    }
+
+    /// The shards a killed Glass enemy leaves behind, other enemies leave nothing
+    pub fn shard_burst(&self, shards: &ShardConfig) -> Option<ShardBurst> {
+        (self.enemy_type == EnemyType::Glass && self.is_dead()).then_some(ShardBurst {
+            pos: self.pos,
+            owner: self.id,
+            time_remaining: shards.telegraph_time,
+            visual_config: ProjectileVisualConfig {
+                primary_color: self.visual_config.circle_color,
+                secondary_color: self.visual_config.circle_color,
+                indicator_color: self.visual_config.indicator_color,
+            },
+        })
+    }
+
+    /// Whether the enemy can hurt the player on contact now, starts its `cooldown` if so
+    pub fn try_contact_hit(&mut self, cooldown: f32) -> bool {
+        if self.contact_cooldown > 0.0 {
+            return false;
+        }
+        self.contact_cooldown = cooldown;
+        true
+    }
+
+    /// Slows the enemy down, overlapping slows keep the stronger factor and the longer duration
+    pub fn apply_slow(&mut self, factor: f32, duration: f32) {
+        if duration <= 0.0 {
+            return;
+        }
+        if self.is_slowed() {
+            self.slow_factor = self.slow_factor.min(factor);
+        } else {
+            self.slow_factor = factor;
+        }
+        self.slow_timer = self.slow_timer.max(duration);
+    }
+
+    /// Ethereal Phasers can't be hit and don't touch the player
+    pub fn is_vulnerable(&self) -> bool {
+        !self.ethereal
+    }
+
+    /// Opacity from the Phaser phase, fading towards the next phase shortly before it changes
+    fn phase_alpha(&self) -> f32 {
+        if self.enemy_type != EnemyType::Phaser {
+            return 1.0;
+        }
+        let (current, next) = if self.ethereal {
+            (PHASER_ETHEREAL_ALPHA, 1.0)
+        } else {
+            (1.0, PHASER_ETHEREAL_ALPHA)
+        };
+        let t = 1.0 - (self.phase_timer / PHASER_FADE_TIME).clamp(0.0, 1.0);
+        current + (next - current) * t
+    }
+
+    fn update_phase(&mut self, dt: f32) {
+        self.phase_timer -= dt;
+        if self.phase_timer <= 0.0 {
+            self.ethereal = !self.ethereal;
+            self.phase_timer += if self.ethereal {
+                PHASER_ETHEREAL_TIME
+            } else {
+                PHASER_SOLID_TIME
+            };
+        }
+    }
+
+    pub fn is_slowed(&self) -> bool {
+        self.slow_timer > 0.0
+    }
+
+    /// Max speed with the Berserker rage and the slow debuff applied
+    pub fn effective_max_speed(&self) -> f32 {
+        let max_speed = self.stats.max_speed * self.rage;
+        if self.is_slowed() {
+            max_speed * self.slow_factor.clamp(0.0, 1.0)
+        } else {
+            max_speed
+        }
+    }
+
+    /// Mass proportional to the enemy's area, used in collisions between enemies
+    pub fn mass(&self) -> f32 {
+        self.stats.radius * self.stats.radius
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0.0
     }
 
     pub fn override_visual_config(&mut self, visual_config: EnemyVisualConfig) {
         self.visual_config = visual_config;
     }
 
-    pub fn draw(&self) {
-        draw_circle(
-            self.pos.x,
-            self.pos.y,
-            self.stats.radius,
-            self.visual_config.circle_color.to_color(),
-        );
+    pub fn draw(&self, alpha: f32, reduced_motion: bool) {
+        let pos = self.prev_pos.lerp(self.pos, alpha);
+        let circle_color = if self.is_slowed() {
+            // Tint slowed enemies towards an icy blue
+            BlendConfig::new(
+                self.visual_config.circle_color,
+                ColorConfig::new(0.5, 0.8, 1.0, 1.0),
+            )
+            .blend(0.6)
+        } else {
+            self.visual_config.circle_color
+        };
+        // Flash white right after a hit
+        let circle_color = if self.hit_flash > 0.0 && !reduced_motion {
+            BlendConfig::new(circle_color, ColorConfig::white())
+                .blend((self.hit_flash / HIT_FLASH_TIME).clamp(0.0, 1.0))
+        } else {
+            circle_color
+        };
+        let phase_alpha = self.phase_alpha();
+        let mut color = circle_color.to_color();
+        color.a *= phase_alpha;
+        draw_circle(pos.x, pos.y, self.stats.radius, color);
 
         // Draw direction indicator triangle
         draw_direction_indicator(
-            self.pos,
+            pos,
             self.vel,
             self.stats.radius,
             self.visual_config.indicator_color,
             self.visual_config.indicator_size,
         );
+
+        self.draw_health_bar(pos);
+
+        // Telegraph a teleport with a ring shrinking onto the enemy
+        if self.telegraph_timer > 0.0 {
+            let t = (self.telegraph_timer / TELEPORT_TELEGRAPH_TIME).clamp(0.0, 1.0);
+            draw_circle_lines(
+                pos.x,
+                pos.y,
+                self.stats.radius * (1.0 + 2.0 * t),
+                2.0,
+                self.visual_config.indicator_color.to_color(),
+            );
+        }
+    }
+
+    fn draw_health_bar(&self, pos: Vec2) {
+        // Only damaged enemies show a health bar, fading out after the last hit
+        if self.health >= self.stats.max_health || self.last_damaged <= 0.0 {
+            return;
+        }
+
+        let alpha = (self.last_damaged / HEALTH_BAR_FADE_TIME).clamp(0.0, 1.0);
+        let health_fraction = (self.health / self.stats.max_health).clamp(0.0, 1.0);
+
+        let blend = BlendConfig::new(ColorConfig::green(), ColorConfig::red());
+        let mut bar_color = blend.blend(1.0 - health_fraction);
+        bar_color.a *= alpha;
+
+        let width = self.visual_config.health_bar_width;
+        let height = self.visual_config.health_bar_height;
+        let x = pos.x - width / 2.0;
+        let y = pos.y - self.stats.radius - self.visual_config.health_bar_offset - height;
+
+        draw_rectangle(x, y, width, height, Color::new(0.2, 0.2, 0.2, 0.6 * alpha));
+        draw_rectangle(x, y, width * health_fraction, height, bar_color.to_color());
     }
 
-    pub fn update(&mut self, player_pos: Option<Vec2>) {
+    pub fn update(&mut self, dt: f32, player_pos: Option<Vec2>) {
+        if self.last_damaged > 0.0 {
+            self.last_damaged -= dt;
+        }
+        if self.slow_timer > 0.0 {
+            self.slow_timer -= dt;
+        }
+        if self.contact_cooldown > 0.0 {
+            self.contact_cooldown -= dt;
+        }
+        if self.hit_flash > 0.0 {
+            self.hit_flash -= dt;
+        }
+
         match self.enemy_type {
-            EnemyType::Basic => self.update_basic(),
+            EnemyType::Basic => self.update_basic(dt),
             EnemyType::Chaser => {
                 if let Some(target) = player_pos {
-                    self.update_chaser(target);
+                    self.update_chaser(dt, target);
+                } else {
+                    self.update_basic(dt);
+                }
+            }
+            EnemyType::Teleporter => {
+                if let Some(target) = player_pos {
+                    self.update_chaser(dt, target);
+                    self.update_teleport(dt, target);
+                } else {
+                    self.update_basic(dt);
+                }
+            }
+            EnemyType::Phaser => {
+                self.update_phase(dt);
+                if let Some(target) = player_pos {
+                    self.update_chaser(dt, target);
+                } else {
+                    self.update_basic(dt);
+                }
+            }
+            EnemyType::Glass => {
+                if let Some(target) = player_pos {
+                    self.update_chaser(dt, target);
                 } else {
-                    self.update_basic();
+                    self.update_basic(dt);
+                }
+            }
+            EnemyType::Builder => {
+                self.barrier_timer -= dt;
+                if let Some(target) = player_pos {
+                    self.update_chaser(dt, target);
+                } else {
+                    self.update_basic(dt);
+                }
+            }
+            EnemyType::Dummy => {
+                self.vel = Vec2::ZERO;
+            }
+            EnemyType::Berserker => {
+                if let Some(target) = player_pos {
+                    self.rage = self.berserker_ramp.multiplier(self.pos.distance(target));
+                    self.update_chaser(dt, target);
+                } else {
+                    self.rage = 1.0;
+                    self.update_basic(dt);
                 }
             }
         }
 
-        self.pos += self.vel;
+        self.pos += self.vel * reference_ticks(dt);
     }
 
-    fn update_basic(&mut self) {
+    fn update_basic(&mut self, dt: f32) {
         // add acceleration in current direction
         let acc_dir = Vec2::new(
             if self.vel.x < 0.0 { -1.0 } else { 1.0 },
             if self.vel.y < 0.0 { -1.0 } else { 1.0 },
         );
-        self.vel += acc_dir * self.stats.acceleration;
+        self.vel += acc_dir * self.stats.acceleration * reference_ticks(dt);
 
         // clamp velocity to max speed
         self.clamp_velocity();
     }
 
-    fn update_chaser(&mut self, player_pos: Vec2) {
+    fn update_chaser(&mut self, dt: f32, player_pos: Vec2) {
+        self.update_reassess(dt);
+
         // Calculate direction to player
         let to_player = player_pos - self.pos;
         let distance = to_player.length();
 
         if distance > 1.0 {
-            let desired_dir = to_player / distance;
-            let desired_vel = desired_dir * self.stats.max_speed;
+            // Approach at an angle, so the swarm spreads around the player
+            let desired_dir = Vec2::from_angle(self.approach_angle).rotate(to_player / distance);
+            let desired_vel = desired_dir * self.effective_max_speed();
 
-            // Steering: gradually adjust velocity toward desired velocity
-            let steering = (desired_vel - self.vel) * self.stats.acceleration;
+            // Steering: gradually adjust velocity toward desired velocity, Berserkers steer harder.
+            // The fraction is per reference tick, compounded to the actual tick length
+            let acceleration = (self.stats.acceleration * self.rage).min(1.0);
+            let acceleration = 1.0 - (1.0 - acceleration).powf(reference_ticks(dt));
+            let steering = (desired_vel - self.vel) * acceleration;
             self.vel += steering;
         }
 
@@ -91,10 +548,105 @@ impl Enemy {
         self.clamp_velocity();
     }
 
+    /// Moves the enemy back onto the edge of the `min`..`max` area and reflects its velocity
+    pub fn bounce_inside(&mut self, min: Vec2, max: Vec2) {
+        if self.pos.x < min.x {
+            self.pos.x = min.x;
+            self.vel.x = self.vel.x.abs();
+        } else if self.pos.x > max.x {
+            self.pos.x = max.x;
+            self.vel.x = -self.vel.x.abs();
+        }
+        if self.pos.y < min.y {
+            self.pos.y = min.y;
+            self.vel.y = self.vel.y.abs();
+        } else if self.pos.y > max.y {
+            self.pos.y = max.y;
+            self.vel.y = -self.vel.y.abs();
+        }
+    }
+
+    /// Moves an enemy that left the `min`..`max` area to the opposite edge
+    pub fn wrap_inside(&mut self, min: Vec2, max: Vec2) {
+        let wrapped = min + wrap_position(self.pos - min, max - min);
+        // Shift the previous position along, so interpolated drawing doesn't sweep across
+        self.prev_pos += wrapped - self.pos;
+        self.pos = wrapped;
+    }
+
+    /// The barrier a Builder puts down once its timer ran out, across its direction of travel
+    pub fn take_barrier(&mut self) -> Option<Barrier> {
+        if self.enemy_type != EnemyType::Builder || self.barrier_timer > 0.0 {
+            return None;
+        }
+        self.barrier_timer = self.barrier.interval.max(0.1);
+        Some(Barrier::across(
+            self.pos,
+            self.vel,
+            &self.barrier,
+            self.visual_config.circle_color,
+        ))
+    }
+
+    /// Picks a new approach angle whenever the reassess timer runs out
+    fn update_reassess(&mut self, dt: f32) {
+        if self.reassess.interval <= 0.0 {
+            self.approach_angle = 0.0;
+            return;
+        }
+        self.reassess_timer -= dt;
+        if self.reassess_timer <= 0.0 {
+            self.reassess_timer = self.reassess.interval;
+            // Below 90° the chaser still closes in, spiralling towards the player
+            let max_angle = self.reassess.max_angle.clamp(0.0, 80.0).to_radians();
+            let mut angle = rand::gen_range(-max_angle, max_angle);
+            if (angle - self.approach_angle).abs() < max_angle / 4.0 {
+                // Avoid picking almost the same line again
+                angle -= angle.signum() * max_angle / 2.0;
+            }
+            self.approach_angle = angle;
+        }
+    }
+
+    fn update_teleport(&mut self, dt: f32, player_pos: Vec2) {
+        if self.telegraph_timer > 0.0 {
+            self.telegraph_timer -= dt;
+            if self.telegraph_timer <= 0.0 {
+                let bounds = Vec2::new(screen_width(), screen_height());
+                if let Some(target) = Self::teleport_target(player_pos, bounds) {
+                    self.pos = target;
+                    // Blink instead of sliding over when drawing interpolated
+                    self.prev_pos = target;
+                    self.vel = Vec2::ZERO;
+                }
+                self.teleport_timer = TELEPORT_COOLDOWN;
+            }
+        } else {
+            self.teleport_timer -= dt;
+            if self.teleport_timer <= 0.0 {
+                self.telegraph_timer = TELEPORT_TELEGRAPH_TIME;
+            }
+        }
+    }
+
+    /// Random point around the player, never closer than TELEPORT_MIN_DISTANCE and inside the screen
+    fn teleport_target(player_pos: Vec2, bounds: Vec2) -> Option<Vec2> {
+        for _ in 0..8 {
+            let angle = rand::gen_range(0.0, std::f32::consts::TAU);
+            let distance = rand::gen_range(TELEPORT_MIN_DISTANCE, TELEPORT_MAX_DISTANCE);
+            let target = player_pos + Vec2::from_angle(angle) * distance;
+            if target.x >= 0.0 && target.x <= bounds.x && target.y >= 0.0 && target.y <= bounds.y {
+                return Some(target);
+            }
+        }
+        None
+    }
+
     fn clamp_velocity(&mut self) {
+        let max_speed = self.effective_max_speed();
         let speed = self.vel.length();
-        if speed > self.stats.max_speed {
-            self.vel = self.vel.normalize() * self.stats.max_speed;
+        if speed > max_speed {
+            self.vel = self.vel.normalize() * max_speed;
         }
     }
 }
@@ -110,3 +662,252 @@ impl Collidable for Enemy {
         self.pos
     }
 }
+
+/// Test fixture shared by the tests of every module
+#[cfg(test)]
+impl Enemy {
+    /// A resting Basic enemy with small round stats
+    pub fn for_test(id: EntityId, pos: Vec2) -> Self {
+        let stats = EntityStats {
+            radius: 10.0,
+            max_speed: 4.0,
+            acceleration: 1.0,
+            friction: 0.0,
+            max_health: 10.0,
+        };
+        Enemy::new(
+            id,
+            EnemyType::Basic,
+            pos,
+            Vec2::ZERO,
+            stats,
+            EnemyVisualConfig::basic_default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enemy_with_radius(radius: f32, vel: Vec2) -> Enemy {
+        let mut enemy = Enemy::for_test(0, Vec2::ZERO);
+        enemy.stats.radius = radius;
+        enemy.vel = vel;
+        enemy
+    }
+
+    #[test]
+    fn test_elastic_collision_conserves_momentum() {
+        let small = enemy_with_radius(5.0, Vec2::new(3.0, 1.0));
+        let big = enemy_with_radius(20.0, Vec2::new(-1.0, 0.5));
+        // Small enemy on the left moving into the big one on the right
+        let normal = Vec2::new(-1.0, 0.0);
+
+        let (small_vel, big_vel) = crate::collision::elastic_collision_velocities(
+            small.vel,
+            small.mass(),
+            big.vel,
+            big.mass(),
+            normal,
+        );
+
+        let momentum_before = small.vel * small.mass() + big.vel * big.mass();
+        let momentum_after = small_vel * small.mass() + big_vel * big.mass();
+        assert!((momentum_before - momentum_after).length() < 1e-3);
+
+        // Kinetic energy is conserved as well
+        let energy_before =
+            small.vel.length_squared() * small.mass() + big.vel.length_squared() * big.mass();
+        let energy_after =
+            small_vel.length_squared() * small.mass() + big_vel.length_squared() * big.mass();
+        assert!((energy_before - energy_after).abs() < 1e-2);
+
+        // The big enemy barely budges, the small one bounces back
+        assert!((big_vel - big.vel).length() < (small_vel - small.vel).length());
+        assert!(small_vel.x < 0.0);
+    }
+
+    #[test]
+    fn test_berserker_ramp_grows_towards_player() {
+        let ramp = BerserkerRamp {
+            start_distance: 200.0,
+            max_multiplier: 3.0,
+        };
+        assert_eq!(ramp.multiplier(300.0), 1.0);
+        assert_eq!(ramp.multiplier(200.0), 1.0);
+        assert!((ramp.multiplier(100.0) - 2.0).abs() < 1e-5);
+        assert!((ramp.multiplier(0.0) - 3.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_chaser_reassesses_approach() {
+        let mut chaser = enemy_with_radius(10.0, Vec2::ZERO);
+        chaser.enemy_type = EnemyType::Chaser;
+        chaser.reassess = ChaserReassess {
+            interval: 1.0,
+            max_angle: 60.0,
+        };
+        chaser.reassess_timer = 1.0;
+        let player_pos = Vec2::new(0.0, 500.0);
+
+        // Straight at the player until the timer runs out
+        chaser.update(0.5, Some(player_pos));
+        assert_eq!(chaser.approach_angle, 0.0);
+
+        let mut angles = Vec::new();
+        for _ in 0..5 {
+            chaser.update(1.0, Some(player_pos));
+            assert!(chaser.approach_angle.abs() <= 60f32.to_radians());
+            angles.push(chaser.approach_angle);
+        }
+        // Every reassessment picks a noticeably different angle
+        assert!(angles[0] != 0.0);
+        for pair in angles.windows(2) {
+            assert!(pair[0] != pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_ethereal_phaser_takes_no_damage() {
+        let mut phaser = enemy_with_radius(10.0, Vec2::ZERO);
+        phaser.enemy_type = EnemyType::Phaser;
+        let max_health = phaser.health;
+
+        // Solid at first, then ethereal once the solid phase ran out
+        assert!(phaser.is_vulnerable());
+        phaser.update(PHASER_SOLID_TIME + 0.01, None);
+        assert!(!phaser.is_vulnerable());
+
+        phaser.take_damage(5.0);
+        assert_eq!(phaser.health, max_health);
+
+        // Solid again after the ethereal phase
+        phaser.update(PHASER_ETHEREAL_TIME, None);
+        assert!(phaser.is_vulnerable());
+        phaser.take_damage(5.0);
+        assert_eq!(phaser.health, max_health - 5.0);
+    }
+
+    #[test]
+    fn test_hit_flash_fades() {
+        let mut enemy = enemy_with_radius(10.0, Vec2::ZERO);
+        assert_eq!(enemy.hit_flash, 0.0);
+
+        enemy.take_damage(1.0);
+        assert_eq!(enemy.hit_flash, HIT_FLASH_TIME);
+
+        for _ in 0..10 {
+            enemy.update(1.0 / 30.0, None);
+        }
+        assert!(enemy.hit_flash <= 0.0);
+    }
+
+    #[test]
+    fn test_slow_limits_speed_and_wears_off() {
+        let mut enemy = enemy_with_radius(10.0, Vec2::new(4.0, 0.0));
+
+        enemy.apply_slow(0.5, 1.0);
+        enemy.update(0.1, None);
+        assert!(enemy.vel.length() <= 2.0 + 1e-4);
+
+        enemy.update(1.0, None);
+        assert!(!enemy.is_slowed());
+        enemy.update(0.1, None);
+        assert!(enemy.vel.length() > 2.0);
+    }
+
+    #[test]
+    fn test_teleport_target_keeps_distance_to_player() {
+        let bounds = Vec2::new(800.0, 800.0);
+        for player_pos in [Vec2::new(400.0, 400.0), Vec2::new(10.0, 10.0)] {
+            for _ in 0..100 {
+                if let Some(target) = Enemy::teleport_target(player_pos, bounds) {
+                    assert!(target.distance(player_pos) >= TELEPORT_MIN_DISTANCE);
+                    assert!(target.x >= 0.0 && target.x <= bounds.x);
+                    assert!(target.y >= 0.0 && target.y <= bounds.y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bounce_reflects_instead_of_leaving() {
+        let min = Vec2::new(-50.0, -50.0);
+        let max = Vec2::new(850.0, 650.0);
+        let mut enemy = enemy_with_radius(10.0, Vec2::new(-3.0, 1.0));
+        enemy.pos = Vec2::new(-60.0, 300.0);
+
+        enemy.bounce_inside(min, max);
+        assert_eq!(enemy.pos, Vec2::new(-50.0, 300.0));
+        assert_eq!(enemy.vel, Vec2::new(3.0, 1.0));
+
+        // An enemy inside the area is left alone
+        enemy.pos = Vec2::new(400.0, 300.0);
+        enemy.bounce_inside(min, max);
+        assert_eq!(enemy.vel, Vec2::new(3.0, 1.0));
+    }
+
+    #[test]
+    fn test_killed_glass_enemy_bursts_into_a_ring() {
+        let config = ShardConfig::default();
+        let mut glass = enemy_with_radius(10.0, Vec2::ZERO);
+        glass.enemy_type = EnemyType::Glass;
+        glass.pos = Vec2::new(50.0, 80.0);
+        assert!(glass.shard_burst(&config).is_none());
+
+        glass.take_damage(glass.health);
+        let burst = glass.shard_burst(&config).unwrap();
+        let shards = burst.shards(&config);
+        assert_eq!(shards.len(), config.count as usize);
+
+        let step = std::f32::consts::TAU / config.count as f32;
+        for (i, shard) in shards.iter().enumerate() {
+            let SpawnCommand::Projectile {
+                pos,
+                vel,
+                visual_config,
+                ..
+            } = shard
+            else {
+                panic!("expected a projectile, got {:?}", shard);
+            };
+            assert_eq!(*pos, glass.pos);
+            // The shards take the colors of the enemy they came from
+            let color = visual_config.unwrap().primary_color;
+            assert_eq!(color.r, glass.visual_config.circle_color.r);
+            assert!((vel.length() - config.speed).abs() < 1e-3);
+            assert!(vel.angle_between(Vec2::from_angle(i as f32 * step)).abs() < 1e-3);
+        }
+
+        // Other enemies don't burst
+        let mut basic = enemy_with_radius(10.0, Vec2::ZERO);
+        basic.take_damage(basic.health);
+        assert!(basic.shard_burst(&config).is_none());
+    }
+
+    #[test]
+    fn test_enemies_from_old_saves_load() {
+        // Saves from before these fields existed lack them
+        let mut json = serde_json::to_value(Enemy::for_test(1, Vec2::ZERO)).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        for field in [
+            "hit_flash",
+            "berserker_ramp",
+            "rage",
+            "phase_timer",
+            "ethereal",
+            "reassess",
+            "reassess_timer",
+            "approach_angle",
+            "barrier",
+            "barrier_timer",
+        ] {
+            fields.remove(field);
+        }
+        let enemy: Enemy = serde_json::from_value(json).unwrap();
+        assert_eq!(enemy.rage, 1.0);
+        assert!(!enemy.ethereal);
+        assert_eq!(enemy.reassess.interval, ChaserReassess::default().interval);
+    }
+}
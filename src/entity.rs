@@ -1,16 +1,49 @@
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::enemy::EnemyType;
-use crate::projectile::{ProjectileStats, ProjectileType};
+use crate::enemy::{Enemy, EnemyType};
+use crate::player::Player;
+use crate::projectile::{Projectile, ProjectileStats, ProjectileType};
+use crate::visual_config::ProjectileVisualConfig;
+use crate::weapon::WeaponType;
 
 pub type EntityId = u64;
 
-#[derive(Debug, Clone, Copy)]
+/// The player always gets the first EntityId, enemies and projectiles follow
+pub const PLAYER_ID: EntityId = 0;
+
+/// Default health, low enough that any projectile kills in one hit
+pub const DEFAULT_MAX_HEALTH: f32 = 10.0;
+
+/// Speeds, accelerations and friction of entities are given per tick at this rate
+pub const REFERENCE_TICK_RATE: f32 = 30.0;
+
+/// Number of reference ticks `dt` stands for, 1.0 at the reference rate
+pub fn reference_ticks(dt: f32) -> f32 {
+    dt * REFERENCE_TICK_RATE
+}
+
+/// Moves `pos` by `vel` and applies `friction` over `dt`. Friction is the velocity factor per
+/// reference tick, the motion is split so that any tick rate covers the same distance.
+pub fn apply_motion(pos: &mut Vec2, vel: &mut Vec2, friction: f32, dt: f32) {
+    let ticks = reference_ticks(dt);
+    if (friction - 1.0).abs() < f32::EPSILON {
+        *pos += *vel * ticks;
+        return;
+    }
+    // Geometric sum of the per-tick steps, one reference tick moves by exactly `vel`
+    let decay = friction.max(0.0).powf(ticks);
+    *pos += *vel * (1.0 - decay) / (1.0 - friction.max(0.0));
+    *vel *= decay;
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct EntityStats {
     pub radius: f32,
     pub max_speed: f32,
     pub acceleration: f32,
     pub friction: f32,
+    pub max_health: f32,
 }
 
 #[derive(Debug)]
@@ -20,9 +53,138 @@ pub enum SpawnCommand {
         pos: Vec2,
         vel: Vec2,
         stats: ProjectileStats,
+        weapon_type: WeaponType, // The weapon that fired the projectile
+        visual_config: Option<ProjectileVisualConfig>, // None for the configured look of the type
     },
     Enemy {
         enemy_type: EnemyType,
         pos: Vec2,
     },
 }
+
+/// Enemy waiting to appear, telegraphed by a marker at its spawn position
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PendingSpawn {
+    pub enemy_type: EnemyType,
+    #[serde(with = "crate::serde_compat::vec2")]
+    pub pos: Vec2,
+    pub time_remaining: f32, // Seconds until the enemy appears
+}
+
+impl PendingSpawn {
+    /// Counts down all telegraphs and takes the spawns whose time has come, at most `room` of them.
+    /// Spawns held back by the cap stay queued in order and appear as soon as there is room.
+    pub fn take_ready(pending: &mut Vec<PendingSpawn>, dt: f32, room: usize) -> Vec<PendingSpawn> {
+        for spawn in pending.iter_mut() {
+            spawn.time_remaining -= dt;
+        }
+
+        let mut ready = Vec::new();
+        pending.retain(|spawn| {
+            if spawn.time_remaining <= 0.0 && ready.len() < room {
+                ready.push(*spawn);
+                false
+            } else {
+                true
+            }
+        });
+        ready
+    }
+}
+
+/// Anything that can be looked up by its EntityId
+pub trait Identified {
+    fn id(&self) -> EntityId;
+}
+
+impl Identified for Enemy {
+    fn id(&self) -> EntityId {
+        self.id
+    }
+}
+
+impl Identified for Projectile {
+    fn id(&self) -> EntityId {
+        self.id
+    }
+}
+
+/// The entity with `id`, the lookup by id every system shares
+pub fn find_by_id<'a, T: Identified + 'a>(
+    entities: impl IntoIterator<Item = &'a T>,
+    id: EntityId,
+) -> Option<&'a T> {
+    entities.into_iter().find(|entity| entity.id() == id)
+}
+
+pub fn find_by_id_mut<'a, T: Identified + 'a>(
+    entities: impl IntoIterator<Item = &'a mut T>,
+    id: EntityId,
+) -> Option<&'a mut T> {
+    entities.into_iter().find(|entity| entity.id() == id)
+}
+
+/// Borrowed reference to any entity in the game, looked up by its EntityId
+#[derive(Clone, Copy)]
+pub enum EntityRef<'a> {
+    Player(&'a Player),
+    Enemy(&'a Enemy),
+    Projectile(&'a Projectile),
+}
+
+impl EntityRef<'_> {
+    /// Position between the last and the current logic step, for drawing
+    pub fn interpolated_position(&self, alpha: f32) -> Vec2 {
+        match self {
+            EntityRef::Player(player) => player.prev_pos.lerp(player.pos, alpha),
+            EntityRef::Enemy(enemy) => enemy.prev_pos.lerp(enemy.pos, alpha),
+            EntityRef::Projectile(projectile) => projectile.prev_pos.lerp(projectile.pos, alpha),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_spawns_respect_enemy_cap() {
+        let cap = 10;
+        let mut pending: Vec<PendingSpawn> = (0..200)
+            .map(|i| PendingSpawn {
+                enemy_type: EnemyType::Basic,
+                pos: Vec2::new(i as f32, 0.0),
+                time_remaining: 0.5,
+            })
+            .collect();
+
+        // Nothing appears before the telegraph ran out
+        assert!(PendingSpawn::take_ready(&mut pending, 0.1, cap).is_empty());
+
+        let mut live = 0;
+        let ready = PendingSpawn::take_ready(&mut pending, 1.0, cap - live);
+        live += ready.len();
+        assert_eq!(live, cap);
+        assert_eq!(pending.len(), 190);
+
+        // A full field holds everything back, killing enemies makes room again
+        assert!(PendingSpawn::take_ready(&mut pending, 0.1, cap - live).is_empty());
+        live -= 3;
+        let ready = PendingSpawn::take_ready(&mut pending, 0.1, cap - live);
+        assert_eq!(ready.len(), 3);
+        // The queue order is kept
+        assert_eq!(ready[0].pos.x, 10.0);
+    }
+
+    #[test]
+    fn test_find_by_id() {
+        let mut enemies: Vec<Enemy> = (1..=3)
+            .map(|id| Enemy::for_test(id, Vec2::new(id as f32, 0.0)))
+            .collect();
+        assert_eq!(find_by_id(&enemies, 2).unwrap().pos.x, 2.0);
+        assert!(find_by_id(&enemies, 9).is_none());
+
+        find_by_id_mut(&mut enemies, 3).unwrap().health = 0.0;
+        assert!(enemies[2].is_dead());
+    }
+}
@@ -1,27 +1,63 @@
 use macroquad::prelude::*;
 
-use super::GameState;
+use super::{DeathCause, GameState};
+use crate::layout::Layout;
 
 pub fn process(gs: &mut GameState) {
     clear_background(BLACK);
 
     super::draw_elf_message(gs);
 
+    let layout = Layout::current();
+    let title_pos = layout.menu(320.0, 560.0);
+    draw_text("GAME OVER", title_pos.x, title_pos.y, layout.s(40.0), RED);
+    draw_run_summary(gs, &layout);
+
+    let prompt_x = layout.menu(300.0, 0.0).x;
     draw_text(
-        "GAME OVER",
-        screen_width() / 2.0 - 80.0,
-        screen_height() / 2.0 + 160.,
-        40.0,
-        RED,
+        "Press Return to Restart",
+        prompt_x,
+        layout.end_prompt_y(0),
+        layout.s(20.0),
+        DARKGRAY,
     );
+    let retry_text = format!("Press R to Retry (same seed {})", gs.seed);
     draw_text(
-        "Press Return to Restart",
-        screen_width() / 2.0 - 100.0,
-        screen_height() / 2.0 + 250.0,
-        20.0,
+        &retry_text,
+        prompt_x,
+        layout.end_prompt_y(1),
+        layout.s(20.0),
         DARKGRAY,
     );
     if is_key_pressed(KeyCode::Enter) {
-        *gs = GameState::new(gs.assets.clone());
+        gs.restart();
+    } else if is_key_pressed(KeyCode::R) {
+        gs.retry_same_seed();
+    }
+}
+
+fn draw_run_summary(gs: &GameState, layout: &Layout) {
+    // What ended the run
+    let cause_text = match gs.killed_by {
+        Some(DeathCause::Enemy(enemy_type)) => format!("Killed by: {:?}", enemy_type),
+        Some(DeathCause::LeftScreen) => "Killed by: leaving the screen".to_owned(),
+        None => "Killed by: unknown".to_owned(),
+    };
+    let cause_pos = layout.menu(300.0, 595.0);
+    draw_text(
+        &cause_text,
+        cause_pos.x,
+        cause_pos.y,
+        layout.s(20.0),
+        LIGHTGRAY,
+    );
+
+    // Kills per weapon, best weapon first
+    let mut kills: Vec<_> = gs.kills_by_weapon.iter().collect();
+    kills.sort_by(|a, b| b.1.cmp(a.1));
+    for (i, (weapon_type, count)) in kills.iter().enumerate() {
+        let kills_text = format!("{:?}: {} kills", weapon_type, count);
+        let kills_pos = layout.menu(320.0, 625.0 + i as f32 * 22.0);
+        draw_text(&kills_text, kills_pos.x, kills_pos.y, layout.s(16.0), GRAY);
     }
 }
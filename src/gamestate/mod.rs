@@ -5,15 +5,58 @@ pub mod weapon_selection;
 pub mod won;
 
 use macroquad::prelude::*;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::barrier::{Barrier, BarrierConfig};
+use crate::collision::{
+    Collidable, Collider, check_collision_wrapped, elastic_collision_velocities, separation_offset,
+    wrap_position,
+};
+use crate::console::Console;
+use crate::damage_meter::{DPS_WINDOW, DamageMeter};
+use crate::enemy::{BerserkerRamp, ChaserReassess, Enemy, EnemyType, ShardBurst, ShardConfig};
+use crate::entity::{
+    DEFAULT_MAX_HEALTH, EntityId, EntityRef, EntityStats, PLAYER_ID, PendingSpawn, SpawnCommand,
+    find_by_id, find_by_id_mut,
+};
+use crate::layout::Layout;
+use crate::palette::{PALETTE_PATH, Palette};
+use crate::pickup::{Pickup, PickupType};
+use crate::player::{AntiCampConfig, Player, WaveReward, WaveRewards};
+use crate::projectile::{Projectile, ProjectilePool, ProjectileStats, ProjectileType};
+use crate::roto_script::{BoundsMode, ContactModel, EnemyBounds, GameConstants, RotoScriptManager};
+use crate::save::{SAVE_PATH, SaveGame};
+use crate::settings::Settings;
+use crate::visual_config::{Assets, EnemyVisualConfig, GameVisualConfig, ProjectileVisualConfig};
+use crate::weapon::{ReplacedProjectiles, Weapon, WeaponTuning, WeaponType};
+
+/// Seconds a floating text like a heal number stays visible
+pub const FLOATING_TEXT_TIME: f32 = 0.8;
+
+/// Short-lived text rising from a position, e.g. heal numbers
+pub struct FloatingText {
+    pub text: String,
+    pub pos: Vec2,
+    pub color: Color,
+    pub time_remaining: f32,
+}
 
-use crate::collision::{Collidable, check_collision};
-use crate::enemy::{Enemy, EnemyType};
-use crate::entity::{EntityId, EntityStats, SpawnCommand};
-use crate::player::Player;
-use crate::projectile::{Projectile, ProjectileStats, ProjectileType};
-use crate::roto_script::{GameConstants, RotoScriptManager};
-use crate::visual_config::{Assets, GameVisualConfig};
+/// Seconds before the same enemy can count as a graze again
+const GRAZE_COOLDOWN: f32 = 2.0;
+/// Seconds the "GRAZE!" popup stays visible
+pub const GRAZE_POPUP_TIME: f32 = 0.6;
+/// Spawn commands the reused buffer keeps room for, a burst above this is freed again afterwards
+pub const SPAWN_BUFFER_CAPACITY: usize = 64;
+/// Simulation speed while the fast-forward debug key is held
+const FAST_FORWARD_SCALE: f64 = 3.0;
+/// Most logic updates per frame, time beyond that is dropped instead of piling up
+const MAX_LOGIC_UPDATES_PER_FRAME: u32 = 8;
+/// Seconds the tether slow lingers after the beam let go
+const TETHER_SLOW_LINGER: f32 = 0.2;
+/// Seconds "0 remaining" is shown before the next wave starts
+pub const WAVE_CLEAR_TIME: f32 = 1.5;
+/// Pixels the stats tab scrolls per wheel notch or arrow key press
+const STATS_SCROLL_STEP: f32 = 22.0;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum GameStateEnum {
@@ -24,17 +67,30 @@ pub enum GameStateEnum {
     Won,
 }
 
+/// What ended the run, for the game over summary
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeathCause {
+    Enemy(EnemyType), // Touched or shot by an enemy of this type
+    LeftScreen,       // Left the screen with deadly bounds
+}
+
 pub struct GameState {
     pub player: Player,
     pub t_frame: f64,
     pub t_prev: f64,
     pub t_passed: f64,
     pub n_logic_updates: u32,
+    pub time_scale: f64, // Simulation speed, above 1.0 while fast-forwarding
     pub enemies: Vec<Enemy>,
-    pub projectiles: Vec<Projectile>,
+    pub pending_spawns: Vec<PendingSpawn>, // Telegraphed enemies that appear soon
+    pub projectiles: ProjectilePool,
     pub state: GameStateEnum,
     pub next_state: Option<GameStateEnum>,
     pub wave: u32,
+    pub wave_enemy_total: u32, // Enemies the current wave spawns, for the remaining count
+    pub wave_clear_timer: f32, // Seconds since the last enemy of the wave is gone
+    pub rewarded_wave: u32,    // Last wave whose reward was picked or skipped
+    pub stillness_timer: f32,  // Seconds the player has been standing still, for the anti-camp
     pub roto_manager: RotoScriptManager,
     pub error_message: Option<String>,
     pub paused: bool,
@@ -42,57 +98,249 @@ pub struct GameState {
     pub game_constants: GameConstants,
     pub basic_enemy_stats: EntityStats,
     pub chaser_enemy_stats: EntityStats,
+    pub teleporter_enemy_stats: EntityStats,
+    pub berserker_enemy_stats: EntityStats,
+    pub berserker_ramp: BerserkerRamp,
+    pub chaser_reassess: ChaserReassess,
+    pub phaser_enemy_stats: EntityStats,
+    pub builder_enemy_stats: EntityStats,
+    pub builder_barrier: BarrierConfig,
+    pub glass_enemy_stats: EntityStats,
+    pub glass_shards: ShardConfig,
+    pub barriers: Vec<Barrier>, // Temporary walls put down by Builders
+    pub shard_bursts: Vec<ShardBurst>, // Killed Glass enemies about to burst into shards
+    pub pickups: Vec<Pickup>,
+    pub weapon_tuning: WeaponTuning,
     pub next_entity_id: EntityId,
-    pub enemies_to_despawn: HashSet<EntityId>,
-    pub projectiles_to_despawn: HashSet<EntityId>,
+    // Ordered sets, so despawns and their effects are processed in the same order every run
+    pub enemies_to_despawn: BTreeSet<EntityId>,
+    pub projectiles_to_despawn: BTreeSet<EntityId>,
     pub message_from_elf: Option<String>,
     pub assets: Assets,
     pub num_lvlups: u32,
+    pub interpolate_rendering: bool,
+    pub kills_by_weapon: HashMap<WeaponType, u32>,
+    pub killed_by: Option<DeathCause>,
+    pub console: Console,
+    pub graze_cooldowns: HashMap<EntityId, f32>, // Recently grazed enemies and their remaining cooldown
+    pub graze_popup_timer: f32,
+    pub settings: Settings,
+    pub floating_texts: Vec<FloatingText>,
+    pub show_aim_line: bool,
+    pub show_cooldown_arcs: bool, // Readiness arcs around the player, toggled with 'F7'
+    pub debug_colliders: bool,
+    pub debug_grid: bool,
+    pub hud_weapon_index: usize, // Weapon whose details the HUD shows, cycled with 'C'
+    pub run_time: f32,           // Seconds of unpaused play in this run
+    pub show_stats: bool,        // Stats tab of the pause screen
+    pub stats_scroll: f32,       // Scroll offset of the stats tab in pixels
+    pub spawn_buffer: Vec<SpawnCommand>, // Reused every tick for the player's spawn commands
+    pub seed: u64,               // RNG seed of this run, a retry starts from it again
+    pub sandbox: bool,           // No waves, practice dummies instead, started with '--sandbox'
+    pub dummy_immune: bool,      // Practice dummies keep their full health, toggled with 'I'
+    pub dummy_damage: DamageMeter, // Damage dealt to the practice dummies, for the DPS readout
 }
 
-impl GameState {
-    pub fn new(assets: Assets) -> Self {
-        let mut roto_manager = RotoScriptManager::new();
+/// Everything a run reads from the script, so a retry can start over without loading it again
+#[derive(Clone, Copy)]
+struct ScriptValues {
+    player_stats: EntityStats,
+    visual_config: GameVisualConfig,
+    game_constants: GameConstants,
+    basic_enemy_stats: EntityStats,
+    chaser_enemy_stats: EntityStats,
+    teleporter_enemy_stats: EntityStats,
+    berserker_enemy_stats: EntityStats,
+    berserker_ramp: BerserkerRamp,
+    chaser_reassess: ChaserReassess,
+    phaser_enemy_stats: EntityStats,
+    builder_enemy_stats: EntityStats,
+    builder_barrier: BarrierConfig,
+    glass_enemy_stats: EntityStats,
+    glass_shards: ShardConfig,
+    weapon_tuning: WeaponTuning,
+}
 
+impl ScriptValues {
+    /// Reads the values from the script, falling back to defaults where that fails
+    fn load(script: &mut RotoScriptManager) -> Self {
         // Try to fetch player stats from Roto, fallback to defaults if it fails
-        let player_stats = roto_manager.get_player_stats().unwrap_or(EntityStats {
+        let player_stats = script.get_player_stats().unwrap_or(EntityStats {
             radius: 20.0,
             max_speed: 5.0,
             acceleration: 1.0,
             friction: 0.9,
+            max_health: DEFAULT_MAX_HEALTH,
         });
 
-        let visual_config = roto_manager
+        let mut visual_config = script
             .get_visual_config()
             .unwrap_or(GameVisualConfig::default());
+        Palette::load(PALETTE_PATH).apply(&mut visual_config);
 
-        let game_constants = roto_manager.get_game_constants().unwrap_or(GameConstants {
+        let game_constants = script.get_game_constants().unwrap_or(GameConstants {
             out_of_bounds_margin: 50.0,
             spawn_target_offset: 100.0,
             max_waves: 30,
+            bounds_mode: BoundsMode::Deadly,
+            enemy_bounds: EnemyBounds::Despawn,
+            graze_margin: 10.0,
+            graze_bonus: 1,
+            spawn_telegraph_time: 0.5,
+            contact_damage: DEFAULT_MAX_HEALTH,
+            contact_model: ContactModel::Global,
+            contact_cooldown: 1.0,
+            max_enemies: 60,
+            knockback: 5.0,
+            replaced_projectiles: ReplacedProjectiles::Keep,
+            shield_drop_chance: 0.0,
+            friendly_fire: false,
+            tick_rate: crate::DEFAULT_TICK_RATE,
+            wave_rewards: WaveRewards::default(),
+            anti_camp: AntiCampConfig::default(),
         });
 
-        let basic_enemy_stats =
-            roto_manager
-                .get_enemy_stats(EnemyType::Basic)
+        let basic_enemy_stats = script
+            .get_enemy_stats(EnemyType::Basic)
+            .unwrap_or(EntityStats {
+                radius: 15.0,
+                max_speed: 3.0,
+                acceleration: 0.5,
+                friction: 0.95,
+                max_health: DEFAULT_MAX_HEALTH,
+            });
+
+        let chaser_enemy_stats = script
+            .get_enemy_stats(EnemyType::Chaser)
+            .unwrap_or(EntityStats {
+                radius: 12.0,
+                max_speed: 4.0,
+                acceleration: 0.8,
+                friction: 0.95,
+                max_health: DEFAULT_MAX_HEALTH,
+            });
+
+        let teleporter_enemy_stats =
+            script
+                .get_enemy_stats(EnemyType::Teleporter)
                 .unwrap_or(EntityStats {
-                    radius: 15.0,
-                    max_speed: 3.0,
-                    acceleration: 0.5,
+                    radius: 13.0,
+                    max_speed: 3.5,
+                    acceleration: 0.2,
                     friction: 0.95,
+                    max_health: DEFAULT_MAX_HEALTH,
                 });
 
-        let chaser_enemy_stats =
-            roto_manager
-                .get_enemy_stats(EnemyType::Chaser)
+        let berserker_enemy_stats =
+            script
+                .get_enemy_stats(EnemyType::Berserker)
                 .unwrap_or(EntityStats {
-                    radius: 12.0,
-                    max_speed: 4.0,
-                    acceleration: 0.8,
+                    radius: 14.0,
+                    max_speed: 3.0,
+                    acceleration: 0.15,
                     friction: 0.95,
+                    max_health: DEFAULT_MAX_HEALTH,
                 });
+        let berserker_ramp = script.get_berserker_ramp().unwrap_or_default();
+        let chaser_reassess = script.get_chaser_reassess().unwrap_or_default();
+        let phaser_enemy_stats = script
+            .get_enemy_stats(EnemyType::Phaser)
+            .unwrap_or(EntityStats {
+                radius: 13.0,
+                max_speed: 3.5,
+                acceleration: 0.2,
+                friction: 0.95,
+                max_health: DEFAULT_MAX_HEALTH,
+            });
+        let builder_enemy_stats =
+            script
+                .get_enemy_stats(EnemyType::Builder)
+                .unwrap_or(EntityStats {
+                    radius: 16.0,
+                    max_speed: 2.0,
+                    acceleration: 0.1,
+                    friction: 0.95,
+                    max_health: DEFAULT_MAX_HEALTH,
+                });
+        let builder_barrier = script.get_builder_barrier().unwrap_or_default();
+        let glass_enemy_stats = script
+            .get_enemy_stats(EnemyType::Glass)
+            .unwrap_or(EntityStats {
+                radius: 14.0,
+                max_speed: 2.5,
+                acceleration: 0.15,
+                friction: 0.0,
+                max_health: DEFAULT_MAX_HEALTH,
+            });
+        let glass_shards = script.get_glass_shards().unwrap_or_default();
+
+        let weapon_tuning = script.get_weapon_tuning().unwrap_or_default();
 
-        let mut player = Player::new(screen_width() / 2.0, screen_height() / 2.0, player_stats);
+        Self {
+            player_stats,
+            visual_config,
+            game_constants,
+            basic_enemy_stats,
+            chaser_enemy_stats,
+            teleporter_enemy_stats,
+            berserker_enemy_stats,
+            berserker_ramp,
+            chaser_reassess,
+            phaser_enemy_stats,
+            builder_enemy_stats,
+            builder_barrier,
+            glass_enemy_stats,
+            glass_shards,
+            weapon_tuning,
+        }
+    }
+}
+
+/// Seed for a new run, taken from the clock so every run is different
+fn new_seed() -> u64 {
+    (miniquad::date::now() * 1000.0) as u64
+}
+
+impl GameState {
+    pub fn new(assets: Assets, script_path: String) -> Self {
+        let mut roto_manager = RotoScriptManager::new(script_path);
+        let values = ScriptValues::load(&mut roto_manager);
+        Self::start_run(assets, roto_manager, values, new_seed())
+    }
+
+    /// Builds a fresh run, `seed` decides the spawns and every other random choice
+    fn start_run(
+        assets: Assets,
+        roto_manager: RotoScriptManager,
+        values: ScriptValues,
+        seed: u64,
+    ) -> Self {
+        rand::srand(seed);
+        let ScriptValues {
+            player_stats,
+            visual_config,
+            game_constants,
+            basic_enemy_stats,
+            chaser_enemy_stats,
+            teleporter_enemy_stats,
+            berserker_enemy_stats,
+            berserker_ramp,
+            chaser_reassess,
+            phaser_enemy_stats,
+            builder_enemy_stats,
+            builder_barrier,
+            glass_enemy_stats,
+            glass_shards,
+            weapon_tuning,
+        } = values;
+
+        let player_id = PLAYER_ID;
+        let mut player = Player::new(
+            player_id,
+            screen_width() / 2.0,
+            screen_height() / 2.0,
+            player_stats,
+        );
         player.override_visual_config(visual_config.player);
 
         let tmp = r##"
@@ -109,11 +357,17 @@ I will summon magic to to beat the evil!.
             t_prev: get_time(),
             t_passed: 0.0,
             n_logic_updates: 0,
+            time_scale: 1.0,
             enemies: vec![],
-            projectiles: vec![],
+            pending_spawns: vec![],
+            projectiles: ProjectilePool::new(),
             state: GameStateEnum::WeaponSelection,
             next_state: None,
             wave: 0,
+            wave_enemy_total: 0,
+            wave_clear_timer: 0.0,
+            rewarded_wave: 0,
+            stillness_timer: 0.0,
             roto_manager,
             error_message: None,
             paused: false,
@@ -121,28 +375,154 @@ I will summon magic to to beat the evil!.
             game_constants,
             basic_enemy_stats,
             chaser_enemy_stats,
-            next_entity_id: 0,
-            enemies_to_despawn: HashSet::new(),
-            projectiles_to_despawn: HashSet::new(),
+            teleporter_enemy_stats,
+            berserker_enemy_stats,
+            berserker_ramp,
+            chaser_reassess,
+            phaser_enemy_stats,
+            builder_enemy_stats,
+            builder_barrier,
+            glass_enemy_stats,
+            glass_shards,
+            barriers: Vec::new(),
+            shard_bursts: Vec::new(),
+            pickups: Vec::new(),
+            weapon_tuning,
+            next_entity_id: player_id + 1,
+            enemies_to_despawn: BTreeSet::new(),
+            projectiles_to_despawn: BTreeSet::new(),
             message_from_elf: Some(tmp.to_owned()),
             assets,
             num_lvlups: 1,
+            interpolate_rendering: true,
+            kills_by_weapon: HashMap::new(),
+            killed_by: None,
+            console: Console::new(),
+            graze_cooldowns: HashMap::new(),
+            graze_popup_timer: 0.0,
+            settings: Settings::load(),
+            floating_texts: Vec::new(),
+            show_aim_line: true,
+            show_cooldown_arcs: true,
+            debug_colliders: false,
+            debug_grid: false,
+            hud_weapon_index: 0,
+            run_time: 0.0,
+            show_stats: false,
+            stats_scroll: 0.0,
+            spawn_buffer: Vec::with_capacity(SPAWN_BUFFER_CAPACITY),
+            seed,
+            sandbox: false,
+            dummy_immune: true,
+            dummy_damage: DamageMeter::new(DPS_WINDOW),
+        }
+    }
+
+    /// The script values this run uses, including changes from a reload
+    fn script_values(&self) -> ScriptValues {
+        ScriptValues {
+            player_stats: self.player.get_stats(),
+            visual_config: self.visual_config,
+            game_constants: self.game_constants,
+            basic_enemy_stats: self.basic_enemy_stats,
+            chaser_enemy_stats: self.chaser_enemy_stats,
+            teleporter_enemy_stats: self.teleporter_enemy_stats,
+            berserker_enemy_stats: self.berserker_enemy_stats,
+            berserker_ramp: self.berserker_ramp,
+            chaser_reassess: self.chaser_reassess,
+            phaser_enemy_stats: self.phaser_enemy_stats,
+            builder_enemy_stats: self.builder_enemy_stats,
+            builder_barrier: self.builder_barrier,
+            glass_enemy_stats: self.glass_enemy_stats,
+            glass_shards: self.glass_shards,
+            weapon_tuning: self.weapon_tuning,
         }
     }
 
+    fn alloc_entity_id(&mut self) -> EntityId {
+        let id = self.next_entity_id;
+        self.next_entity_id += 1;
+        id
+    }
+
+    /// Looks up any entity (player, enemy or projectile) by its id
+    pub fn entity(&self, id: EntityId) -> Option<EntityRef<'_>> {
+        if self.player.id == id {
+            return Some(EntityRef::Player(&self.player));
+        }
+        if let Some(enemy) = find_by_id(&self.enemies, id) {
+            return Some(EntityRef::Enemy(enemy));
+        }
+        find_by_id(self.projectiles.iter(), id).map(EntityRef::Projectile)
+    }
+
+    /// Returns the XP earned from kills and grazes
     pub fn check_collisions(&mut self) -> u32 {
         // Check player-enemy collisions
         let mut game_over = false;
-        for enemy in &self.enemies {
-            let collision_data = check_collision(
+        let mut graze_xp = 0;
+        let bounds = self.wrap_bounds();
+        // A slightly larger player circle catches the near misses
+        let graze_collider = Collider::Circle {
+            radius: self.player.get_radius() + self.game_constants.graze_margin,
+        };
+        let contact_model = self.game_constants.contact_model;
+        for enemy in &mut self.enemies {
+            // Ethereal enemies pass through the player, practice dummies never touch it
+            if !enemy.is_vulnerable() || enemy.is_harmless() {
+                continue;
+            }
+            let collision_data = check_collision_wrapped(
                 &self.player.collider(),
                 self.player.position(),
                 &enemy.collider(),
                 enemy.position(),
+                bounds,
             );
             if collision_data.collided {
-                game_over = true;
-                self.enemies_to_despawn.insert(enemy.id);
+                match contact_model {
+                    // The enemy is destroyed on contact, the player only gets hurt outside the i-frames
+                    ContactModel::Global => {
+                        self.enemies_to_despawn.insert(enemy.id);
+                    }
+                    // The enemy stays and only hits again once its own cooldown ran out
+                    ContactModel::PerEnemy => {
+                        if !enemy.try_contact_hit(self.game_constants.contact_cooldown) {
+                            continue;
+                        }
+                    }
+                }
+                if self.player.absorb_with_shield() {
+                    // The shield breaks instead, the player is still pushed away
+                    self.player
+                        .apply_knockback(collision_data.normal, self.game_constants.knockback);
+                } else if self
+                    .player
+                    .take_contact_damage(self.game_constants.contact_damage, contact_model)
+                {
+                    // The normal points from the enemy to the player
+                    self.player
+                        .apply_knockback(collision_data.normal, self.game_constants.knockback);
+                    if self.player.is_dead() {
+                        game_over = true;
+                        // Remember what killed us for the game over summary
+                        self.killed_by
+                            .get_or_insert(DeathCause::Enemy(enemy.enemy_type));
+                    }
+                }
+            } else if !self.graze_cooldowns.contains_key(&enemy.id)
+                && check_collision_wrapped(
+                    &graze_collider,
+                    self.player.position(),
+                    &enemy.collider(),
+                    enemy.position(),
+                    bounds,
+                )
+                .collided
+            {
+                graze_xp += self.game_constants.graze_bonus;
+                self.graze_cooldowns.insert(enemy.id, GRAZE_COOLDOWN);
+                self.graze_popup_timer = GRAZE_POPUP_TIME;
             }
         }
 
@@ -150,15 +530,241 @@ I will summon magic to to beat the evil!.
             self.set_next_state(GameStateEnum::GameOver);
         }
 
+        // Players are only hit by projectiles of other players, and only with friendly fire on
+        self.check_projectile_player_collisions();
+
         // Check enemy-enemy collisions with elastic bounce
         self.check_enemy_collisions();
 
+        // Barriers stop projectiles before they reach the enemies behind
+        self.check_barrier_collisions();
+
         // Check projectile-enemy collisions
-        self.check_projectile_enemy_collisions()
+        self.check_projectile_enemy_collisions() + graze_xp
+    }
+
+    /// Counts down the pickups and hands the touched ones to the player
+    pub fn update_pickups(&mut self, dt: f32) {
+        let bounds = self.wrap_bounds();
+        let player = &mut self.player;
+        self.pickups.retain_mut(|pickup| {
+            pickup.update(dt);
+            let collected = check_collision_wrapped(
+                &player.collider(),
+                player.position(),
+                &pickup.collider(),
+                pickup.position(),
+                bounds,
+            )
+            .collided;
+            if collected {
+                match pickup.pickup_type {
+                    PickupType::Shield => player.shield_charges += 1,
+                }
+            }
+            !collected && !pickup.is_expired()
+        });
+    }
+
+    /// Tethers damage and slow their linked enemy every tick, returns the bonus XP for the
+    /// enemies they killed like the projectile hits do
+    pub fn update_tethers(&mut self, dt: f32) -> u32 {
+        let mut kills = Vec::new();
+        let mut lifesteal_heal = 0.0;
+        let player_pos = self.player.pos;
+        for weapon in self.player.get_weapons_mut().iter_mut() {
+            if weapon.weapon_type != WeaponType::Tether {
+                continue;
+            }
+            let Some(target_id) =
+                weapon.update_tether_target(player_pos, &self.enemies, &self.barriers)
+            else {
+                continue;
+            };
+            let Some(enemy) = find_by_id_mut(&mut self.enemies, target_id) else {
+                continue;
+            };
+            if !enemy.is_vulnerable()
+                || enemy.is_dead()
+                || self.enemies_to_despawn.contains(&enemy.id)
+            {
+                continue;
+            }
+
+            let tether = weapon.stats.tether;
+            let damage = tether.dps * dt;
+            enemy.take_damage(damage);
+            enemy.apply_slow(tether.slow_factor, TETHER_SLOW_LINGER);
+            lifesteal_heal += weapon.lifesteal_heal(damage);
+
+            if enemy.is_dead() {
+                kills.push((enemy.id, enemy.pos));
+                weapon.tether_target = None;
+            }
+        }
+
+        // No heal number here, it would pop up every tick
+        self.player.heal(lifesteal_heal);
+        let killed_enemies = kills.len() as u32;
+        for (enemy_id, pos) in kills {
+            self.on_enemy_killed(enemy_id, pos, WeaponType::Tether);
+        }
+        killed_enemies
+    }
+
+    /// Despawns an enemy killed by `weapon_type`, rolls its shield drop and counts the kill
+    fn on_enemy_killed(&mut self, enemy_id: EntityId, pos: Vec2, weapon_type: WeaponType) {
+        self.enemies_to_despawn.insert(enemy_id);
+        if rand::gen_range(0.0, 1.0) < self.game_constants.shield_drop_chance {
+            self.pickups.push(Pickup::new(PickupType::Shield, pos));
+        }
+        *self.kills_by_weapon.entry(weapon_type).or_insert(0) += 1;
+        if let Some(weapon) = self
+            .player
+            .get_weapons_mut()
+            .iter_mut()
+            .find(|w| w.weapon_type == weapon_type)
+        {
+            weapon.add_kill();
+        }
+    }
+
+    /// Feeds the damage the practice dummies took into the DPS readout, immune dummies
+    /// heal back up right away
+    pub fn update_dummies(&mut self) {
+        for enemy in self.enemies.iter_mut() {
+            if enemy.enemy_type != EnemyType::Dummy {
+                continue;
+            }
+            self.dummy_damage
+                .record(self.run_time, enemy.drain_damage_taken());
+            if self.dummy_immune {
+                enemy.health = enemy.stats.max_health;
+            }
+        }
+    }
+
+    /// Counts down the cracking Glass enemies and fires the shards of those whose time is up
+    pub fn update_shard_bursts(&mut self, dt: f32) {
+        let mut ready = Vec::new();
+        self.shard_bursts.retain_mut(|burst| {
+            burst.time_remaining -= dt;
+            if burst.time_remaining <= 0.0 {
+                ready.push(*burst);
+                false
+            } else {
+                true
+            }
+        });
+        for burst in ready {
+            let shards = burst.shards(&self.glass_shards);
+            self.execute_spawn_commands(burst.owner, shards);
+        }
+    }
+
+    /// Gravity wells drag the enemies within their radius towards the center
+    pub fn apply_gravity_wells(&mut self, dt: f32) {
+        let wells = self
+            .projectiles
+            .iter()
+            .filter(|p| p.projectile_type == ProjectileType::GravityWell);
+        for well in wells {
+            for enemy in self.enemies.iter_mut() {
+                enemy.vel += well.pull_on(enemy.pos, dt);
+            }
+        }
+    }
+
+    /// Collects the barriers Builders put down and removes the expired ones
+    pub fn update_barriers(&mut self, dt: f32) {
+        for barrier in self.barriers.iter_mut() {
+            barrier.update(dt);
+        }
+        self.barriers.retain(|barrier| !barrier.is_expired());
+        for enemy in self.enemies.iter_mut() {
+            if let Some(barrier) = enemy.take_barrier() {
+                self.barriers.push(barrier);
+            }
+        }
+    }
+
+    /// Barriers push the player out and stop the projectiles flying into them
+    fn check_barrier_collisions(&mut self) {
+        let bounds = self.wrap_bounds();
+        for barrier in &self.barriers {
+            let collision_data = check_collision_wrapped(
+                &self.player.collider(),
+                self.player.position(),
+                &barrier.collider(),
+                barrier.position(),
+                bounds,
+            );
+            if collision_data.collided {
+                // The normal points from the barrier to the player
+                let normal = collision_data.normal;
+                self.player.pos += normal * collision_data.penetration_depth;
+                let into_barrier = self.player.vel.dot(normal);
+                if into_barrier < 0.0 {
+                    self.player.vel -= normal * into_barrier;
+                }
+            }
+
+            for projectile in self.projectiles.iter() {
+                if barrier.blocks(projectile, bounds) {
+                    self.projectiles_to_despawn.insert(projectile.id);
+                }
+            }
+        }
+    }
+
+    /// Counts down the graze cooldowns and the popup
+    pub fn update_graze_timers(&mut self, dt: f32) {
+        self.graze_cooldowns.retain(|_, cooldown| {
+            *cooldown -= dt;
+            *cooldown > 0.0
+        });
+        if self.graze_popup_timer > 0.0 {
+            self.graze_popup_timer -= dt;
+        }
+    }
+
+    fn check_projectile_player_collisions(&mut self) {
+        let bounds = self.wrap_bounds();
+        let friendly_fire = self.game_constants.friendly_fire;
+        let mut game_over = false;
+        for projectile in self.projectiles.iter() {
+            if !projectile.can_hit_player(self.player.id, friendly_fire)
+                || self.projectiles_to_despawn.contains(&projectile.id)
+            {
+                continue;
+            }
+            let collision_data = check_collision_wrapped(
+                &projectile.collider(),
+                projectile.position(),
+                &self.player.collider(),
+                self.player.position(),
+                bounds,
+            );
+            if collision_data.collided && self.player.take_damage(projectile.damage()) {
+                if projectile.projectile_type != ProjectileType::Pulse {
+                    self.projectiles_to_despawn.insert(projectile.id);
+                }
+                if self.player.is_dead() {
+                    game_over = true;
+                    // Shards of a Glass enemy are the only projectiles that hit the player
+                    self.killed_by
+                        .get_or_insert(DeathCause::Enemy(EnemyType::Glass));
+                }
+            }
+        }
+        if game_over {
+            self.set_next_state(GameStateEnum::GameOver);
+        }
     }
 
     fn check_enemy_collisions(&mut self) {
         let num_enemies = self.enemies.len();
+        let bounds = self.wrap_bounds();
 
         for i in 0..num_enemies {
             for j in (i + 1)..num_enemies {
@@ -168,32 +774,38 @@ I will summon magic to to beat the evil!.
                     (enemy1.pos, enemy1.vel, enemy2.pos, enemy2.vel)
                 };
 
-                let collision_data = check_collision(
+                let collision_data = check_collision_wrapped(
                     &self.enemies[i].collider(),
                     pos1,
                     &self.enemies[j].collider(),
                     pos2,
+                    bounds,
                 );
 
                 if collision_data.collided {
-                    // Elastic collision response (equal mass)
+                    // Push overlapping enemies apart, a bit each tick
+                    let offset = separation_offset(&collision_data);
+                    self.enemies[i].pos += offset;
+                    self.enemies[j].pos -= offset;
+
+                    // Elastic collision response, masses derived from the enemy size
                     // Normal points from enemy2 to enemy1
                     let normal = collision_data.normal;
 
-                    // Calculate relative velocity
-                    let rel_vel = vel1 - vel2;
-
                     // Calculate relative velocity along collision normal
-                    let vel_along_normal = rel_vel.dot(normal);
+                    let vel_along_normal = (vel1 - vel2).dot(normal);
 
                     // Do not resolve if velocities are separating
                     if vel_along_normal < 0.0 {
-                        // For elastic collision with equal mass, exchange normal components
-                        let impulse = normal * vel_along_normal;
-
-                        // Apply impulse to both enemies
-                        self.enemies[i].vel -= impulse;
-                        self.enemies[j].vel += impulse;
+                        let (new_vel1, new_vel2) = elastic_collision_velocities(
+                            vel1,
+                            self.enemies[i].mass(),
+                            vel2,
+                            self.enemies[j].mass(),
+                            normal,
+                        );
+                        self.enemies[i].vel = new_vel1;
+                        self.enemies[j].vel = new_vel2;
                     }
                 }
             }
@@ -201,79 +813,240 @@ I will summon magic to to beat the evil!.
     }
 
     fn check_projectile_enemy_collisions(&mut self) -> u32 {
-        let mut killed_enemies = 0;
-        for projectile in &self.projectiles {
-            for enemy in &self.enemies {
-                let collision_data = check_collision(
+        let mut kills = Vec::new();
+        let mut lifesteal_heal = 0.0;
+        let bounds = self.wrap_bounds();
+        for projectile in self.projectiles.iter_mut() {
+            // Gravity wells only pull, see apply_gravity_wells, enemy projectiles fly through enemies
+            if projectile.projectile_type == ProjectileType::GravityWell || projectile.hostile {
+                continue;
+            }
+            for enemy in self.enemies.iter_mut() {
+                // Spent projectiles and dead enemies don't interact anymore
+                if self.projectiles_to_despawn.contains(&projectile.id) {
+                    break;
+                }
+                // Ethereal enemies let projectiles pass through
+                if enemy.is_dead()
+                    || self.enemies_to_despawn.contains(&enemy.id)
+                    || projectile.has_hit(enemy.id)
+                    || !enemy.is_vulnerable()
+                {
+                    continue;
+                }
+
+                let collision_data = check_collision_wrapped(
                     &projectile.collider(),
                     projectile.position(),
                     &enemy.collider(),
                     enemy.position(),
+                    bounds,
                 );
 
                 if collision_data.collided {
-                    killed_enemies += 1;
-                    self.enemies_to_despawn.insert(enemy.id);
-                    // we killed it by ourselves, one more xp:
+                    projectile.hit_enemies.push(enemy.id);
+                    let damage = projectile.damage_at(enemy.pos);
+                    enemy.take_damage(damage);
+                    if let Some(weapon) = self
+                        .player
+                        .get_weapons()
+                        .iter()
+                        .find(|w| w.weapon_type == projectile.weapon_type)
+                    {
+                        lifesteal_heal += weapon.lifesteal_heal(damage);
+                    }
+                    enemy.apply_slow(projectile.stats.slow.factor, projectile.stats.slow.duration);
+
+                    if enemy.is_dead() {
+                        // we killed it by ourselves, one more xp:
+                        kills.push((enemy.id, enemy.pos, projectile.weapon_type));
+                    }
 
                     // Energy balls get removed on hit, pulses stay
                     match projectile.projectile_type {
                         ProjectileType::EnergyBall | ProjectileType::HomingMissile => {
                             self.projectiles_to_despawn.insert(projectile.id);
                         }
-                        ProjectileType::Pulse => {
+                        ProjectileType::Pulse | ProjectileType::GravityWell => {
                             // Pulse continues to exist and can hit multiple enemies
                         }
                     }
                 }
             }
         }
+
+        let healed = self.player.heal(lifesteal_heal);
+        if healed > 0.0 {
+            self.spawn_floating_text(format!("+{:.1}", healed), self.player.pos, GREEN);
+        }
+
+        let killed_enemies = kills.len() as u32;
+        for (enemy_id, pos, weapon_type) in kills {
+            self.on_enemy_killed(enemy_id, pos, weapon_type);
+        }
         killed_enemies
     }
 
+    /// Whether the reward pick after a cleared wave is shown, the next wave waits for it.
+    /// The final wave has no reward, the run is won right away.
+    pub fn wave_reward_open(&self) -> bool {
+        self.wave > self.rewarded_wave
+            && self.wave < self.game_constants.max_waves
+            && self.remaining_enemies() == 0
+            && self.wave_clear_timer >= WAVE_CLEAR_TIME
+    }
+
+    /// Applies the reward picked in the wave break, `None` skips it
+    pub fn claim_wave_reward(&mut self, reward: Option<WaveReward>) {
+        self.rewarded_wave = self.wave;
+        let Some(reward) = reward else {
+            return;
+        };
+
+        let rewards = self.game_constants.wave_rewards;
+        let health_before = self.player.health;
+        let level_ups = self.player.claim_wave_reward(reward, &rewards);
+        let text = match reward {
+            WaveReward::Heal => format!("+{:.0}", self.player.health - health_before),
+            WaveReward::FireRateBuff => format!("x{:.1} fire rate", rewards.buff_multiplier),
+            WaveReward::BonusXp => format!("+{} XP", rewards.bonus_xp),
+        };
+        self.spawn_floating_text(text, self.player.pos, GREEN);
+
+        if level_ups > 0 {
+            self.num_lvlups += level_ups;
+            self.set_next_state(GameStateEnum::WeaponSelection);
+        }
+    }
+
+    pub fn spawn_floating_text(&mut self, text: String, pos: Vec2, color: Color) {
+        self.floating_texts.push(FloatingText {
+            text,
+            pos,
+            color,
+            time_remaining: FLOATING_TEXT_TIME,
+        });
+    }
+
+    pub fn update_floating_texts(&mut self, dt: f32) {
+        self.floating_texts.retain_mut(|text| {
+            text.time_remaining -= dt;
+            text.time_remaining > 0.0
+        });
+    }
+
     pub fn check_player_bounds(&mut self) {
-        let w = screen_width();
-        let h = screen_height();
+        // There is no outside in a wrapping world
+        if self.game_constants.bounds_mode == BoundsMode::Wrap {
+            return;
+        }
 
-        if self.player.pos.x < 0.0
-            || self.player.pos.x > w
-            || self.player.pos.y < 0.0
-            || self.player.pos.y > h
-        {
+        let screen = Vec2::new(screen_width(), screen_height());
+        if player_left_screen(&mut self.player, screen) {
+            self.killed_by.get_or_insert(DeathCause::LeftScreen);
             self.set_next_state(GameStateEnum::GameOver);
         }
     }
 
+    /// Screen size if the world edges wrap around, used by wrap-aware collision checks
+    pub fn wrap_bounds(&self) -> Option<Vec2> {
+        match self.game_constants.bounds_mode {
+            BoundsMode::Wrap => Some(Vec2::new(screen_width(), screen_height())),
+            BoundsMode::Deadly => None,
+        }
+    }
+
+    /// Moves entities that left the screen to the opposite edge
+    pub fn wrap_positions(&mut self) {
+        let Some(bounds) = self.wrap_bounds() else {
+            return;
+        };
+
+        // Shift the previous position along, so interpolated drawing doesn't sweep across the screen
+        fn wrap(pos: &mut Vec2, prev_pos: &mut Vec2, bounds: Vec2) {
+            let wrapped = wrap_position(*pos, bounds);
+            *prev_pos += wrapped - *pos;
+            *pos = wrapped;
+        }
+
+        wrap(&mut self.player.pos, &mut self.player.prev_pos, bounds);
+        for enemy in self.enemies.iter_mut() {
+            wrap(&mut enemy.pos, &mut enemy.prev_pos, bounds);
+        }
+        for projectile in self.projectiles.iter_mut() {
+            wrap(&mut projectile.pos, &mut projectile.prev_pos, bounds);
+        }
+    }
+
     fn is_in_bounds(pos: Vec2, margin: f32) -> bool {
         let w = screen_width();
         let h = screen_height();
         pos.x >= -margin && pos.x <= w + margin && pos.y >= -margin && pos.y <= h + margin
     }
 
+    /// Despawns, bounces or wraps enemies beyond the margin, depending on `enemy_bounds`
     pub fn despawn_enemies_out_of_bounds(&mut self) {
+        if self.game_constants.bounds_mode == BoundsMode::Wrap {
+            return;
+        }
         let margin = self.game_constants.out_of_bounds_margin;
-
-        for enemy in &self.enemies {
-            if !Self::is_in_bounds(enemy.pos, margin) {
-                self.enemies_to_despawn.insert(enemy.id);
+        let min = Vec2::splat(-margin);
+        let max = Vec2::new(screen_width() + margin, screen_height() + margin);
+
+        match self.game_constants.enemy_bounds {
+            EnemyBounds::Despawn => {
+                for enemy in &self.enemies {
+                    if !Self::is_in_bounds(enemy.pos, margin) {
+                        self.enemies_to_despawn.insert(enemy.id);
+                    }
+                }
+            }
+            EnemyBounds::Bounce => {
+                for enemy in self.enemies.iter_mut() {
+                    enemy.bounce_inside(min, max);
+                }
+            }
+            EnemyBounds::Wrap => {
+                for enemy in self.enemies.iter_mut() {
+                    enemy.wrap_inside(min, max);
+                }
             }
         }
     }
 
+    /// Seconds per logic update at the configured tick rate
+    pub fn dt(&self) -> f64 {
+        1.0 / self.game_constants.tick_rate.max(1) as f64
+    }
+
+    /// Enemies of the current wave that are alive or still telegraphed
+    pub fn remaining_enemies(&self) -> u32 {
+        (self.enemies.len() + self.pending_spawns.len()) as u32
+    }
+
     pub fn update_time_for_logic(&mut self) -> u32 {
         // update time counters
         self.t_frame = get_time();
-        self.t_passed += self.t_frame - self.t_prev;
+
+        // A minimized window reports a zero size, the logic is frozen until it is restored.
+        // Moving t_prev along keeps the time spent minimized out of the accumulator
+        if Layout::current().is_degenerate() {
+            self.t_prev = self.t_frame;
+            return 0;
+        }
+        self.t_passed += (self.t_frame - self.t_prev) * self.time_scale;
 
         // update logic at fixed time steps
-        while self.t_passed >= crate::DT {
-            self.t_passed -= crate::DT;
+        let dt = self.dt();
+        while self.t_passed >= dt {
+            self.t_passed -= dt;
             self.n_logic_updates += 1;
         }
 
-        let reval = self.n_logic_updates;
+        let reval = self.n_logic_updates.min(MAX_LOGIC_UPDATES_PER_FRAME);
         if self.n_logic_updates > 0 {
-            if self.n_logic_updates > 1 {
+            // Fast-forwarding runs several updates per frame on purpose
+            if self.n_logic_updates > 1 && self.time_scale <= 1.0 {
                 println!("logic updates: {} - LOW FRAME RATE", self.n_logic_updates);
             }
             self.n_logic_updates = 0;
@@ -283,7 +1056,42 @@ I will summon magic to to beat the evil!.
         reval
     }
 
+    /// Remembers where every entity was before the logic step, so drawing can interpolate
+    pub fn store_previous_positions(&mut self) {
+        self.player.prev_pos = self.player.pos;
+        for enemy in self.enemies.iter_mut() {
+            enemy.prev_pos = enemy.pos;
+        }
+        for projectile in self.projectiles.iter_mut() {
+            projectile.prev_pos = projectile.pos;
+        }
+    }
+
+    /// Fraction of the next logic step that has already passed, used to interpolate drawing
+    pub fn render_alpha(&self) -> f32 {
+        if !self.interpolate_rendering || self.paused || self.state != GameStateEnum::Playing {
+            return 1.0;
+        }
+        (self.t_passed / self.dt()).clamp(0.0, 1.0) as f32
+    }
+
     pub fn process_global_input(&mut self) {
+        // Toggle fullscreen on 'F11', everything reads the live screen size so layout follows
+        if is_key_pressed(KeyCode::F11) {
+            self.settings.fullscreen = !self.settings.fullscreen;
+            set_fullscreen(self.settings.fullscreen);
+            self.settings.save();
+        }
+
+        // Toggle the debug console on backtick, it takes all typed keys while open
+        if is_key_pressed(KeyCode::GraveAccent) {
+            self.console.toggle();
+        }
+        if self.console.open {
+            self.console.process_input(&mut self.roto_manager);
+            return;
+        }
+
         // Hot reload Roto scripts on 'R' key
         if is_key_pressed(KeyCode::R) {
             self.reload_roto_scripts();
@@ -294,6 +1102,103 @@ I will summon magic to to beat the evil!.
             self.paused = !self.paused;
         }
 
+        // Switch to the stats tab with 'Tab' and scroll it with the mouse wheel or arrow keys
+        if self.paused {
+            if is_key_pressed(KeyCode::Tab) {
+                self.show_stats = !self.show_stats;
+                self.stats_scroll = 0.0;
+            }
+            if self.show_stats {
+                let mut scroll = 0.0;
+                let wheel = mouse_wheel().1;
+                if wheel != 0.0 {
+                    scroll -= wheel.signum() * STATS_SCROLL_STEP;
+                }
+                if is_key_pressed(KeyCode::Down) {
+                    scroll += STATS_SCROLL_STEP;
+                }
+                if is_key_pressed(KeyCode::Up) {
+                    scroll -= STATS_SCROLL_STEP;
+                }
+                let max_scroll = playing::stats_max_scroll(self, &Layout::current());
+                self.stats_scroll = (self.stats_scroll + scroll).clamp(0.0, max_scroll);
+            }
+        }
+
+        // Toggle reduced motion with 'M' from the pause screen
+        if self.paused && is_key_pressed(KeyCode::M) {
+            self.settings.reduced_motion = !self.settings.reduced_motion;
+            self.settings.save();
+        }
+
+        // Toggle the per-wave background shift with 'B' from the pause screen
+        if self.paused && is_key_pressed(KeyCode::B) {
+            self.settings.static_background = !self.settings.static_background;
+            self.settings.save();
+        }
+
+        // Save with 'S' and load with 'L' from the pause screen
+        if self.paused && is_key_pressed(KeyCode::S) {
+            match self.save_game(SAVE_PATH) {
+                Ok(()) => println!("✓ Saved the game to {}", SAVE_PATH),
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+        if self.paused && is_key_pressed(KeyCode::L) {
+            match self.load_game(SAVE_PATH) {
+                Ok(()) => println!("✓ Loaded the game from {}", SAVE_PATH),
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+
+        // Toggle collider overlay on 'F2' key, for debugging
+        if is_key_pressed(KeyCode::F2) {
+            self.debug_colliders = !self.debug_colliders;
+        }
+
+        // Toggle render interpolation on 'F3' key, for debugging
+        if is_key_pressed(KeyCode::F3) {
+            self.interpolate_rendering = !self.interpolate_rendering;
+        }
+
+        // Fast-forward the simulation while 'F5' is held, only in debug builds
+        if cfg!(debug_assertions) {
+            self.time_scale = if is_key_down(KeyCode::F5) {
+                FAST_FORWARD_SCALE
+            } else {
+                1.0
+            };
+        }
+
+        // Toggle the coordinate grid on 'F6' key, only in debug builds
+        if cfg!(debug_assertions) && is_key_pressed(KeyCode::F6) {
+            self.debug_grid = !self.debug_grid;
+        }
+
+        // Place a practice dummy at the cursor with 'N' and toggle its immunity with 'I'
+        if self.sandbox && is_key_pressed(KeyCode::N) {
+            self.spawn_dummy(mouse_position().into());
+        }
+        if self.sandbox && is_key_pressed(KeyCode::I) {
+            self.dummy_immune = !self.dummy_immune;
+        }
+
+        // Cycle the weapon the HUD shows details for on 'C' key
+        if is_key_pressed(KeyCode::C) {
+            let count = self.player.get_weapons().len().max(1);
+            self.hud_weapon_index = (self.hud_weapon_slot() + 1) % count;
+        }
+
+        // Toggle the aim line on 'F4' key
+        if is_key_pressed(KeyCode::F4) {
+            self.show_aim_line = !self.show_aim_line;
+        }
+
+        // Toggle the cooldown arcs around the player on 'F7' key
+        if is_key_pressed(KeyCode::F7) {
+            self.show_cooldown_arcs = !self.show_cooldown_arcs;
+        }
+
         if is_key_pressed(KeyCode::X) {
             self.num_lvlups = self.player.add_xp(100);
             if self.num_lvlups > 0 {
@@ -325,17 +1230,27 @@ I will summon magic to to beat the evil!.
         self.game_constants = self.roto_manager.get_game_constants()?;
         self.basic_enemy_stats = self.roto_manager.get_enemy_stats(EnemyType::Basic)?;
         self.chaser_enemy_stats = self.roto_manager.get_enemy_stats(EnemyType::Chaser)?;
-
-        for enemy in self.enemies.iter_mut() {
-            let stats = match enemy.enemy_type {
-                EnemyType::Basic => self.basic_enemy_stats,
-                EnemyType::Chaser => self.chaser_enemy_stats,
-            };
-            enemy.override_stats(stats);
+        self.teleporter_enemy_stats = self.roto_manager.get_enemy_stats(EnemyType::Teleporter)?;
+        self.berserker_enemy_stats = self.roto_manager.get_enemy_stats(EnemyType::Berserker)?;
+        self.berserker_ramp = self.roto_manager.get_berserker_ramp()?;
+        self.chaser_reassess = self.roto_manager.get_chaser_reassess()?;
+        self.phaser_enemy_stats = self.roto_manager.get_enemy_stats(EnemyType::Phaser)?;
+        self.builder_enemy_stats = self.roto_manager.get_enemy_stats(EnemyType::Builder)?;
+        self.builder_barrier = self.roto_manager.get_builder_barrier()?;
+        self.glass_enemy_stats = self.roto_manager.get_enemy_stats(EnemyType::Glass)?;
+        self.glass_shards = self.roto_manager.get_glass_shards()?;
+
+        for i in 0..self.enemies.len() {
+            let stats = self.enemy_stats(self.enemies[i].enemy_type);
+            self.enemies[i].override_stats(stats);
         }
 
+        self.weapon_tuning = self.roto_manager.get_weapon_tuning()?;
+        self.player.override_weapon_tuning(self.weapon_tuning);
+
         // Reload visual configuration
         self.visual_config = self.roto_manager.get_visual_config()?;
+        Palette::load(PALETTE_PATH).apply(&mut self.visual_config);
 
         // Override visual configs for existing entities
         self.player
@@ -350,21 +1265,22 @@ I will summon magic to to beat the evil!.
         Ok(())
     }
 
+    // Mirrors the fields of SpawnCommand::Projectile plus who fired it
+    #[allow(clippy::too_many_arguments)]
     fn spawn_projectile(
         &mut self,
         projectile_type: ProjectileType,
         pos: Vec2,
         vel: Vec2,
         stats: ProjectileStats,
+        weapon_type: WeaponType,
+        owner: EntityId,
+        visual_config: ProjectileVisualConfig,
     ) {
-        let id = self.next_entity_id;
-        self.next_entity_id += 1;
+        let id = self.alloc_entity_id();
 
-        let visual_config = match projectile_type {
-            ProjectileType::EnergyBall => self.visual_config.energy_ball,
-            ProjectileType::Pulse => self.visual_config.pulse,
-            ProjectileType::HomingMissile => self.visual_config.homing_missile,
-        };
+        // Everything not fired by the player comes from an enemy, like the shards of a Glass enemy
+        let hostile = owner != self.player.id;
 
         let projectile = match projectile_type {
             ProjectileType::EnergyBall => {
@@ -372,54 +1288,122 @@ I will summon magic to to beat the evil!.
                 Projectile {
                     id,
                     pos,
+                    prev_pos: pos,
                     vel: normalized_vel,
                     projectile_type: ProjectileType::EnergyBall,
                     stats,
                     time_remaining: stats.time_to_live,
                     source_pos: pos,
                     visual_config,
+                    weapon_type,
+                    owner,
+                    hit_enemies: Vec::new(),
+                    pulse_size: Projectile::pulse_size_at(&stats, stats.time_to_live),
+                    pulse_blend: self.visual_config.pulse_blend,
+                    hostile,
                 }
             }
             ProjectileType::Pulse => Projectile {
                 id,
                 pos,
+                prev_pos: pos,
                 vel: Vec2::ZERO,
                 projectile_type: ProjectileType::Pulse,
                 stats,
                 time_remaining: stats.time_to_live,
                 source_pos: pos,
                 visual_config,
+                weapon_type,
+                owner,
+                hit_enemies: Vec::new(),
+                pulse_size: Projectile::pulse_size_at(&stats, stats.time_to_live),
+                pulse_blend: self.visual_config.pulse_blend,
+                hostile,
             },
             ProjectileType::HomingMissile => {
                 let normalized_vel = vel.normalize() * stats.speed;
                 Projectile {
                     id,
                     pos,
+                    prev_pos: pos,
                     vel: normalized_vel,
                     projectile_type: ProjectileType::HomingMissile,
                     stats,
                     time_remaining: stats.time_to_live,
                     source_pos: pos,
                     visual_config,
+                    weapon_type,
+                    owner,
+                    hit_enemies: Vec::new(),
+                    pulse_size: Projectile::pulse_size_at(&stats, stats.time_to_live),
+                    pulse_blend: self.visual_config.pulse_blend,
+                    hostile,
                 }
             }
+            ProjectileType::GravityWell => Projectile {
+                id,
+                pos,
+                prev_pos: pos,
+                vel: Vec2::ZERO,
+                projectile_type: ProjectileType::GravityWell,
+                stats,
+                time_remaining: stats.time_to_live,
+                source_pos: pos,
+                visual_config,
+                weapon_type,
+                owner,
+                hit_enemies: Vec::new(),
+                pulse_size: Projectile::pulse_size_at(&stats, stats.time_to_live),
+                pulse_blend: self.visual_config.pulse_blend,
+                hostile,
+            },
         };
 
-        self.projectiles.push(projectile);
+        self.projectiles.spawn(projectile);
     }
 
-    pub fn spawn_enemy(&mut self, enemy_type: EnemyType, pos: Vec2) -> Result<(), String> {
-        let id = self.next_entity_id;
-        self.next_entity_id += 1;
+    /// Slot of the weapon the HUD shows details for, the last one if the index is past the
+    /// equipped weapons after a change
+    pub fn hud_weapon_slot(&self) -> usize {
+        let count = self.player.get_weapons().len();
+        self.hud_weapon_index.min(count.saturating_sub(1))
+    }
 
-        let stats = match enemy_type {
+    pub fn hud_weapon(&self) -> Option<&Weapon> {
+        self.player.get_weapons().get(self.hud_weapon_slot())
+    }
+
+    pub fn enemy_stats(&self, enemy_type: EnemyType) -> EntityStats {
+        match enemy_type {
             EnemyType::Basic => self.basic_enemy_stats,
             EnemyType::Chaser => self.chaser_enemy_stats,
-        };
-        let visual_config = match enemy_type {
+            EnemyType::Teleporter => self.teleporter_enemy_stats,
+            EnemyType::Berserker => self.berserker_enemy_stats,
+            EnemyType::Phaser => self.phaser_enemy_stats,
+            EnemyType::Builder => self.builder_enemy_stats,
+            EnemyType::Glass => self.glass_enemy_stats,
+            EnemyType::Dummy => Enemy::dummy_stats(),
+        }
+    }
+
+    pub fn enemy_visual_config(&self, enemy_type: EnemyType) -> EnemyVisualConfig {
+        match enemy_type {
             EnemyType::Basic => self.visual_config.basic_enemy,
             EnemyType::Chaser => self.visual_config.chaser_enemy,
-        };
+            EnemyType::Teleporter => self.visual_config.teleporter_enemy,
+            EnemyType::Berserker => self.visual_config.berserker_enemy,
+            EnemyType::Phaser => self.visual_config.phaser_enemy,
+            EnemyType::Builder => self.visual_config.builder_enemy,
+            EnemyType::Glass => self.visual_config.glass_enemy,
+            EnemyType::Dummy => EnemyVisualConfig::dummy_default(),
+        }
+    }
+
+    pub fn spawn_enemy(&mut self, enemy_type: EnemyType, pos: Vec2) -> Result<(), String> {
+        let id = self.alloc_entity_id();
+
+        let stats = self.enemy_stats(enemy_type);
+        let visual_config = self.enemy_visual_config(enemy_type);
 
         // Calculate random velocity toward center of screen with offset
         let tx = screen_width() / 2.0
@@ -434,24 +1418,107 @@ I will summon magic to to beat the evil!.
             );
 
         let target = Vec2::new(tx, ty);
-        let dir = (target - pos).normalize();
+        let dir = (target - pos).normalize_or_zero();
         let speed = rand::gen_range(1.0, stats.max_speed);
         let vel = dir * speed;
 
-        let enemy = Enemy {
+        let mut enemy = Enemy::new(id, enemy_type, pos, vel, stats, visual_config);
+        enemy.berserker_ramp = self.berserker_ramp;
+        enemy.reassess = self.chaser_reassess;
+        enemy.reassess_timer = self.chaser_reassess.interval;
+        enemy.barrier = self.builder_barrier;
+        enemy.barrier_timer = self.builder_barrier.interval;
+        self.enemies.push(enemy);
+        Ok(())
+    }
+
+    /// Places a practice dummy, only in sandbox mode
+    pub fn spawn_dummy(&mut self, pos: Vec2) {
+        if !self.sandbox {
+            return;
+        }
+        let id = self.alloc_entity_id();
+        self.enemies.push(Enemy::new(
             id,
+            EnemyType::Dummy,
             pos,
-            vel,
+            Vec2::ZERO,
+            self.enemy_stats(EnemyType::Dummy),
+            self.enemy_visual_config(EnemyType::Dummy),
+        ));
+    }
+
+    pub fn projectile_visual_config(
+        &self,
+        projectile_type: ProjectileType,
+    ) -> ProjectileVisualConfig {
+        match projectile_type {
+            ProjectileType::EnergyBall => self.visual_config.energy_ball,
+            ProjectileType::Pulse => self.visual_config.pulse,
+            ProjectileType::HomingMissile => self.visual_config.homing_missile,
+            ProjectileType::GravityWell => self.visual_config.gravity_well,
+        }
+    }
+
+    /// Replaces the weapon at `index` and handles its projectiles per `GameConstants::replaced_projectiles`
+    pub fn replace_weapon(&mut self, index: usize, weapon_type: WeaponType) {
+        if let Some(replaced) = self
+            .player
+            .replace_weapon(index, weapon_type, &self.weapon_tuning)
+        {
+            self.projectiles
+                .on_weapon_replaced(replaced, self.game_constants.replaced_projectiles);
+        }
+    }
+
+    /// Telegraphs an enemy at `pos`, it appears after the spawn telegraph time and counts
+    /// towards the enemies of the current wave
+    pub fn queue_enemy_spawn(&mut self, enemy_type: EnemyType, pos: Vec2) {
+        self.pending_spawns.push(PendingSpawn {
             enemy_type,
-            stats,
-            visual_config,
+            pos,
+            time_remaining: self.game_constants.spawn_telegraph_time,
+        });
+        self.wave_enemy_total += 1;
+    }
+
+    /// Queues chasers around a player who stood still for too long, only while a wave is on
+    pub fn update_anti_camp(&mut self, dt: f32) {
+        let anti_camp = self.game_constants.anti_camp;
+        let speed = if self.remaining_enemies() > 0 {
+            self.player.vel.length()
+        } else {
+            // Waiting out the wave break is fine
+            f32::INFINITY
         };
+        if !anti_camp.update(&mut self.stillness_timer, speed, dt) {
+            return;
+        }
 
-        self.enemies.push(enemy);
-        Ok(())
+        let bounds = Vec2::new(screen_width(), screen_height());
+        for pos in anti_camp.spawn_positions(self.player.pos, bounds) {
+            self.queue_enemy_spawn(EnemyType::Chaser, pos);
+        }
+    }
+
+    /// Counts down the telegraphs and spawns the enemies whose time has come,
+    /// as long as the live enemy count stays below the cap
+    pub fn update_pending_spawns(&mut self, dt: f32) {
+        let cap = self.game_constants.max_enemies as usize;
+        let room = cap.saturating_sub(self.enemies.len());
+        for pending in PendingSpawn::take_ready(&mut self.pending_spawns, dt, room) {
+            if let Err(err) = self.spawn_enemy(pending.enemy_type, pending.pos) {
+                eprintln!("Failed to spawn enemy: {}", err);
+            }
+        }
     }
 
-    pub fn execute_spawn_commands(&mut self, commands: Vec<SpawnCommand>) {
+    /// Spawns what `owner` asked for, the projectiles remember who fired them
+    pub fn execute_spawn_commands(
+        &mut self,
+        owner: EntityId,
+        commands: impl IntoIterator<Item = SpawnCommand>,
+    ) {
         for command in commands {
             match command {
                 SpawnCommand::Projectile {
@@ -459,27 +1526,144 @@ I will summon magic to to beat the evil!.
                     pos,
                     vel,
                     stats,
+                    weapon_type,
+                    visual_config,
                 } => {
-                    self.spawn_projectile(projectile_type, pos, vel, stats);
+                    let visual_config = visual_config
+                        .unwrap_or_else(|| self.projectile_visual_config(projectile_type));
+                    self.spawn_projectile(
+                        projectile_type,
+                        pos,
+                        vel,
+                        stats,
+                        weapon_type,
+                        owner,
+                        visual_config,
+                    );
                 }
                 SpawnCommand::Enemy { enemy_type, pos } => {
-                    if let Err(err) = self.spawn_enemy(enemy_type, pos) {
-                        eprintln!("Failed to spawn enemy: {}", err);
-                    }
+                    self.queue_enemy_spawn(enemy_type, pos);
                 }
             }
         }
     }
 
     pub fn process_despawns(&mut self) {
-        self.enemies_to_despawn.len() as u32;
+        let bursts = despawn_entities(
+            &mut self.enemies,
+            &mut self.projectiles,
+            &self.enemies_to_despawn,
+            &self.projectiles_to_despawn,
+            &self.glass_shards,
+        );
+        self.shard_bursts.extend(bursts);
+        self.enemies_to_despawn.clear();
+        self.projectiles_to_despawn.clear();
+    }
+
+    /// Writes the running game to `path` as JSON
+    pub fn save_game(&self, path: &str) -> Result<(), String> {
+        let save = SaveGame {
+            player: self.player.clone(),
+            enemies: self.enemies.clone(),
+            projectiles: self.projectiles.iter().cloned().collect(),
+            pending_spawns: self.pending_spawns.clone(),
+            wave: self.wave,
+            next_entity_id: self.next_entity_id,
+            kills_by_weapon: self.kills_by_weapon.clone(),
+            run_time: self.run_time,
+            seed: self.seed,
+            shard_bursts: self.shard_bursts.clone(),
+            pickups: self.pickups.clone(),
+            barriers: self.barriers.clone(),
+        };
+        let json = serde_json::to_string_pretty(&save)
+            .map_err(|err| format!("ERROR serializing the game: {}", err))?;
+        std::fs::write(path, json).map_err(|err| format!("ERROR writing {}: {}", path, err))
+    }
 
-        self.enemies
-            .retain(|e| !self.enemies_to_despawn.contains(&e.id));
-        self.projectiles
-            .retain(|p| !self.projectiles_to_despawn.contains(&p.id));
+    /// Continues the game saved at `path`, stats and visuals come from the reloaded Roto script
+    pub fn load_game(&mut self, path: &str) -> Result<(), String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|err| format!("ERROR reading {}: {}", path, err))?;
+        let save: SaveGame = serde_json::from_str(&json)
+            .map_err(|err| format!("ERROR parsing {}: {}", path, err))?;
+
+        self.player = save.player;
+        self.enemies = save.enemies;
+        self.projectiles = ProjectilePool::new();
+        for projectile in save.projectiles {
+            self.projectiles.spawn(projectile);
+        }
+        self.pending_spawns = save.pending_spawns;
+        self.wave = save.wave;
+        self.next_entity_id = save.next_entity_id;
+        self.kills_by_weapon = save.kills_by_weapon;
+        self.run_time = save.run_time;
+        self.seed = save.seed;
+        self.wave_enemy_total = self.remaining_enemies();
+        self.wave_clear_timer = 0.0;
+        // A game saved during the wave break doesn't offer the reward a second time
+        self.rewarded_wave = self.wave;
+        self.barriers = save.barriers;
+        self.shard_bursts = save.shard_bursts;
+        self.dummy_damage.clear();
+        self.pickups = save.pickups;
         self.enemies_to_despawn.clear();
         self.projectiles_to_despawn.clear();
+
+        // Reload the script and hand the current visuals to the loaded entities,
+        // a broken script shows the script error screen as usual
+        self.reload_roto_scripts();
+        for i in 0..self.enemies.len() {
+            let visual_config = self.enemy_visual_config(self.enemies[i].enemy_type);
+            self.enemies[i].override_visual_config(visual_config);
+        }
+        let pulse_blend = self.visual_config.pulse_blend;
+        let visual_configs = [
+            ProjectileType::EnergyBall,
+            ProjectileType::Pulse,
+            ProjectileType::HomingMissile,
+            ProjectileType::GravityWell,
+        ]
+        .map(|projectile_type| {
+            (
+                projectile_type,
+                self.projectile_visual_config(projectile_type),
+            )
+        });
+        for projectile in self.projectiles.iter_mut() {
+            if let Some((_, visual_config)) = visual_configs
+                .iter()
+                .find(|(projectile_type, _)| *projectile_type == projectile.projectile_type)
+            {
+                projectile.visual_config = *visual_config;
+            }
+            projectile.pulse_blend = pulse_blend;
+        }
+        Ok(())
+    }
+
+    /// Starts a new run with the same assets and script file
+    pub fn restart(&mut self) {
+        let script_path = self.roto_manager.script_path().to_owned();
+        let sandbox = self.sandbox;
+        *self = GameState::new(self.assets.clone(), script_path);
+        self.sandbox = sandbox;
+    }
+
+    /// Starts this run over with the same seed, keeping the loaded script and assets
+    pub fn retry_same_seed(&mut self) {
+        let placeholder = RotoScriptManager::unloaded(self.roto_manager.script_path().to_owned());
+        let roto_manager = std::mem::replace(&mut self.roto_manager, placeholder);
+        let sandbox = self.sandbox;
+        *self = GameState::start_run(
+            self.assets.clone(),
+            roto_manager,
+            self.script_values(),
+            self.seed,
+        );
+        self.sandbox = sandbox;
     }
 
     pub fn set_next_state(&mut self, next_state: GameStateEnum) {
@@ -575,9 +1759,12 @@ Just save the file and Press Return here!
     }
 
     pub fn despawn_projectiles_out_of_bounds(&mut self) {
+        if self.game_constants.bounds_mode == BoundsMode::Wrap {
+            return;
+        }
         let margin = self.game_constants.out_of_bounds_margin;
 
-        for projectile in &self.projectiles {
+        for projectile in self.projectiles.iter() {
             // Only remove energy balls and homing missiles that go out of bounds, keep pulses
             match projectile.projectile_type {
                 ProjectileType::EnergyBall | ProjectileType::HomingMissile => {
@@ -585,40 +1772,156 @@ Just save the file and Press Return here!
                         self.projectiles_to_despawn.insert(projectile.id);
                     }
                 }
-                ProjectileType::Pulse => {
-                    // Pulses stay centered on player
+                ProjectileType::Pulse | ProjectileType::GravityWell => {
+                    // Pulses stay centered on player, wells stay where they were placed
                 }
             }
         }
     }
 }
 
+/// Whether the player is outside of `screen`. A knockback must not throw the player out into
+/// an instant death, so right after a hit the player is clamped to the screen instead.
+fn player_left_screen(player: &mut Player, screen: Vec2) -> bool {
+    if player.is_held_in_bounds() {
+        player.pos = player.pos.clamp(Vec2::ZERO, screen);
+        return false;
+    }
+    player.pos.x < 0.0 || player.pos.x > screen.x || player.pos.y < 0.0 || player.pos.y > screen.y
+}
+
+/// Removes the despawned enemies and projectiles, returns the shard bursts of killed Glass
+/// enemies. The ordered id sets make the result the same however the despawns were queued.
+fn despawn_entities(
+    enemies: &mut Vec<Enemy>,
+    projectiles: &mut ProjectilePool,
+    enemy_ids: &BTreeSet<EntityId>,
+    projectile_ids: &BTreeSet<EntityId>,
+    glass_shards: &ShardConfig,
+) -> Vec<ShardBurst> {
+    // Killed Glass enemies crack and burst into shards a moment later
+    let bursts = enemies
+        .iter()
+        .filter(|enemy| enemy_ids.contains(&enemy.id))
+        .filter_map(|enemy| enemy.shard_burst(glass_shards))
+        .collect();
+    enemies.retain(|enemy| !enemy_ids.contains(&enemy.id));
+    projectiles.despawn(projectile_ids);
+    bursts
+}
+
 pub fn draw_elf_message(gs: &GameState) -> bool {
     if let Some(msg) = &gs.message_from_elf {
         let texture = &gs.assets.char_tex.as_ref().unwrap();
 
+        let layout = Layout::current();
         let mut params = DrawTextureParams::default();
-        let (w, h, s) = (texture.width(), texture.height(), 0.33);
-        let x = 0.;
-        let y = 0.;
+        let (w, h, s) = (texture.width(), texture.height(), layout.s(0.33));
+        let origin = layout.menu(0., 0.);
         params.dest_size = Some(Vec2::new(w * s, h * s));
 
-        draw_texture_ex(texture, x, y, WHITE, params);
+        draw_texture_ex(texture, origin.x, origin.y, WHITE, params);
 
-        let x = 300.;
-        let y = 60.;
-        draw_text("The Guardian:", x, y, 32., YELLOW);
+        let title_pos = layout.menu(300., 60.);
+        draw_text(
+            "The Guardian:",
+            title_pos.x,
+            title_pos.y,
+            layout.s(32.),
+            YELLOW,
+        );
 
-        let y = 100.;
+        let text_pos = layout.menu(300., 100.);
         msg.split('.')
             .filter(|sentence| !sentence.is_empty())
             .enumerate()
             .for_each(|(i, sentence)| {
                 let line = sentence.trim();
-                draw_text(line, x, y + i as f32 * 22., 20., WHITE);
+                let y = text_pos.y + layout.s(i as f32 * 22.);
+                draw_text(line, text_pos.x, y, layout.s(20.), WHITE);
             });
         true
     } else {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_despawn_order_is_deterministic() {
+        // The same despawns queued in a different order leave the same entities behind
+        let run = |order: &[EntityId]| {
+            let mut enemies: Vec<Enemy> = (1..=5)
+                .map(|id| Enemy::for_test(id, Vec2::new(id as f32 * 10.0, 0.0)))
+                .collect();
+            for enemy in enemies.iter_mut() {
+                enemy.enemy_type = EnemyType::Glass;
+                enemy.health = 0.0;
+            }
+            let mut projectiles = ProjectilePool::new();
+            for id in 10..18 {
+                projectiles.spawn(Projectile::for_test(id, ProjectileType::Pulse, Vec2::ZERO));
+            }
+
+            let mut enemy_ids = BTreeSet::new();
+            let mut projectile_ids = BTreeSet::new();
+            for &id in order {
+                if id < 10 {
+                    enemy_ids.insert(id);
+                } else {
+                    projectile_ids.insert(id);
+                }
+            }
+            let bursts = despawn_entities(
+                &mut enemies,
+                &mut projectiles,
+                &enemy_ids,
+                &projectile_ids,
+                &ShardConfig::default(),
+            );
+
+            // Respawns fill the freed slots in a fixed order
+            for id in 100..102 {
+                projectiles.spawn(Projectile::for_test(id, ProjectileType::Pulse, Vec2::ZERO));
+            }
+            let enemy_ids: Vec<EntityId> = enemies.iter().map(|e| e.id).collect();
+            let projectile_ids: Vec<EntityId> = projectiles.iter().map(|p| p.id).collect();
+            let burst_owners: Vec<EntityId> = bursts.iter().map(|b| b.owner).collect();
+            (enemy_ids, projectile_ids, burst_owners)
+        };
+
+        let a = run(&[4, 15, 2, 11]);
+        let b = run(&[11, 2, 15, 4]);
+        assert_eq!(a.0, vec![1, 3, 5]);
+        assert_eq!(a.2, vec![2, 4]);
+        let mut projectile_ids = a.1.clone();
+        projectile_ids.sort();
+        assert_eq!(projectile_ids, vec![10, 12, 13, 14, 16, 17, 100, 101]);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_per_enemy_hit_at_the_edge_keeps_the_player_inside() {
+        let stats = EntityStats {
+            radius: 20.0,
+            max_speed: 5.0,
+            acceleration: 1.0,
+            friction: 0.9,
+            max_health: 30.0,
+        };
+        let screen = Vec2::new(800.0, 600.0);
+        let mut player = Player::new(PLAYER_ID, screen.x - 1.0, 300.0, stats);
+
+        // Hit from the left without i-frames, the knockback pushes towards the right edge
+        assert!(player.take_contact_damage(10.0, ContactModel::PerEnemy));
+        assert!(!player.is_invulnerable());
+        player.apply_knockback(Vec2::new(1.0, 0.0), 5.0);
+        player.update(1.0 / 30.0, &[], &mut Vec::new());
+
+        assert!(!player_left_screen(&mut player, screen));
+        assert!(player.pos.x <= screen.x);
+    }
+}
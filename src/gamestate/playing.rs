@@ -1,34 +1,65 @@
 use macroquad::prelude::*;
 
 use super::GameState;
-use crate::DT;
+use crate::collision::{Collidable, Collider};
+use crate::damage_meter::DPS_WINDOW;
 use crate::enemy::EnemyType;
-use crate::gamestate::GameStateEnum;
-use crate::roto_script::WaveConfig;
+use crate::gamestate::weapon_selection::{draw_weapon_icon, get_weapon_color};
+use crate::gamestate::{
+    FLOATING_TEXT_TIME, GRAZE_POPUP_TIME, GameStateEnum, SPAWN_BUFFER_CAPACITY, WAVE_CLEAR_TIME,
+};
+use crate::layout::Layout;
+use crate::player::WaveReward;
+use crate::roto_script::{BoundsMode, WaveConfig};
+use crate::weapon::{FireMode, MAX_WEAPON_SLOTS};
+
+/// Gap between the player circle and the cooldown arcs
+const COOLDOWN_ARC_OFFSET: f32 = 10.0;
+/// Degrees left empty between two cooldown arcs
+const COOLDOWN_ARC_GAP: f32 = 12.0;
+
+/// Pixels between two lines of the debug grid
+const GRID_SPACING: f32 = 50.0;
+/// Every how many grid lines the coordinate is written next to it
+const GRID_LABEL_EVERY: u32 = 4;
 
 pub fn process(gs: &mut GameState) {
-    // Check if we need to spawn a new wave
-    if gs.enemies.is_empty() {
+    // Nothing spawns while the window is minimized, the time update freezes the logic as well
+    if Layout::current().is_degenerate() {
+        gs.update_time_for_logic();
+        return;
+    }
+
+    // Check if we need to spawn a new wave, telegraphed enemies still count as alive.
+    // A cleared wave shows "0 remaining" for a moment before the next one starts
+    // The sandbox has no waves, only the dummies placed by hand
+    let wave_cleared = gs.wave == 0 || gs.wave_clear_timer >= WAVE_CLEAR_TIME;
+    if !gs.sandbox && gs.remaining_enemies() == 0 && wave_cleared {
         // Check if player has won (completed final wave)
         if gs.wave >= gs.game_constants.max_waves {
             gs.set_next_state(super::GameStateEnum::Won);
             return;
         }
 
-        let wave = gs.wave;
-        match gs.roto_manager.get_wave_config(wave) {
-            Ok(config) => {
-                if let Err(err) = spawn_wave(gs, config) {
+        // The next wave waits until the reward of the cleared one is picked or skipped
+        if gs.wave_reward_open() {
+            process_wave_reward(gs);
+        } else {
+            let wave = gs.wave;
+            match gs.roto_manager.get_wave_config(wave) {
+                Ok(config) => {
+                    if let Err(err) = spawn_wave(gs, config) {
+                        gs.set_next_state(super::GameStateEnum::ScriptError);
+                        gs.error_message = Some(err);
+                    } else {
+                        gs.wave += 1;
+                    }
+                }
+                Err(err) => {
                     gs.set_next_state(super::GameStateEnum::ScriptError);
                     gs.error_message = Some(err);
-                } else {
-                    gs.wave += 1;
                 }
             }
-            Err(err) => {
-                gs.set_next_state(super::GameStateEnum::ScriptError);
-                gs.error_message = Some(err);
-            }
         }
     }
 
@@ -36,23 +67,59 @@ pub fn process(gs: &mut GameState) {
     let num_updates = gs.update_time_for_logic();
     for _ in 0..num_updates {
         if !gs.paused {
-            gs.player.input();
+            gs.player.input(gs.dt() as f32, gs.console.open);
             update_logic(gs);
         }
     }
 }
 
+/// Number keys pick a reward from the wave break, Enter skips it
+fn process_wave_reward(gs: &mut GameState) {
+    if gs.paused || gs.console.open {
+        return;
+    }
+    if is_key_pressed(KeyCode::Key1) {
+        gs.claim_wave_reward(Some(WaveReward::Heal));
+    } else if is_key_pressed(KeyCode::Key2) {
+        gs.claim_wave_reward(Some(WaveReward::FireRateBuff));
+    } else if is_key_pressed(KeyCode::Key3) {
+        gs.claim_wave_reward(Some(WaveReward::BonusXp));
+    } else if is_key_pressed(KeyCode::Enter) {
+        gs.claim_wave_reward(None);
+    }
+}
+
 pub fn update_logic(gs: &mut GameState) {
-    let dt = DT as f32;
+    let dt = gs.dt() as f32;
+
+    gs.store_previous_positions();
+    gs.run_time += dt;
 
     // Update player and get spawn commands from weapon firing
-    let spawn_commands = gs.player.update(dt);
-    gs.execute_spawn_commands(spawn_commands);
+    // The spawn buffer is reused every tick instead of allocating a new Vec
+    let mut spawn_commands = std::mem::take(&mut gs.spawn_buffer);
+    gs.player.update(dt, &gs.enemies, &mut spawn_commands);
+    gs.execute_spawn_commands(gs.player.id, spawn_commands.drain(..));
+    spawn_commands.shrink_to(SPAWN_BUFFER_CAPACITY);
+    gs.spawn_buffer = spawn_commands;
+
+    // Telegraphed enemies whose marker ran out appear now
+    if !gs.sandbox {
+        gs.update_anti_camp(dt);
+    }
+    gs.update_pending_spawns(dt);
+    if gs.remaining_enemies() == 0 {
+        gs.wave_clear_timer += dt;
+    }
 
     let player_pos = gs.player.pos;
     for enemy in gs.enemies.iter_mut() {
-        enemy.update(Some(player_pos));
+        enemy.update(dt, Some(player_pos));
     }
+    gs.apply_gravity_wells(dt);
+    let tether_xp = gs.update_tethers(dt);
+    gs.update_shard_bursts(dt);
+    gs.update_barriers(dt);
 
     // Update projectiles
     for projectile in gs.projectiles.iter_mut() {
@@ -62,24 +129,29 @@ pub fn update_logic(gs: &mut GameState) {
     }
 
     // Mark expired projectiles for despawn
-    for projectile in &gs.projectiles {
+    for projectile in gs.projectiles.iter() {
         if projectile.is_expired() {
             gs.projectiles_to_despawn.insert(projectile.id);
         }
     }
 
-    // Mark out-of-bounds entities for despawn
+    // Wrap entities around the screen edges, or mark out-of-bounds entities for despawn
+    gs.wrap_positions();
     gs.despawn_projectiles_out_of_bounds();
     gs.despawn_enemies_out_of_bounds();
 
     // This may trigger game over
-    let num_kills = gs.check_collisions();
+    gs.update_graze_timers(dt);
+    gs.update_floating_texts(dt);
+    gs.update_pickups(dt);
+    let earned_xp = gs.check_collisions() + tether_xp;
     gs.check_player_bounds();
+    gs.update_dummies();
 
     // leveling:
     let enemies_died = gs.enemies_to_despawn.len() as u32;
-    // Award 1 XP per enemy killed
-    let leveled_up = gs.player.add_xp(enemies_died + num_kills);
+    // Award 1 XP per enemy killed, plus the kill and graze bonus XP
+    let leveled_up = gs.player.add_xp(enemies_died + earned_xp);
     gs.num_lvlups = leveled_up;
 
     // If player leveled up, transition to weapon selection
@@ -91,82 +163,747 @@ pub fn update_logic(gs: &mut GameState) {
     gs.process_despawns();
 }
 
+/// Background color shifting from the first to the final wave, or the first one if static
+fn background_color(gs: &GameState) -> Color {
+    let background = gs.visual_config.background;
+    if gs.settings.static_background {
+        return background.inner_color.to_color();
+    }
+    let last_wave = gs.game_constants.max_waves.saturating_sub(1).max(1);
+    let t = gs.wave.saturating_sub(1) as f32 / last_wave as f32;
+    background.blend(t).to_color()
+}
+
 pub fn draw(gs: &GameState) {
-    gs.player.draw();
+    clear_background(background_color(gs));
+    if gs.debug_grid {
+        draw_grid();
+    }
+    // Entities are drawn between their last two logic positions for smooth motion
+    let alpha = gs.render_alpha();
+    draw_spawn_telegraphs(gs);
+    draw_aim_line(gs, alpha);
+    for barrier in gs.barriers.iter() {
+        barrier.draw();
+    }
+    for pickup in gs.pickups.iter() {
+        pickup.draw(gs.settings.reduced_motion);
+    }
+    let reduced_motion = gs.settings.reduced_motion;
+    gs.player.draw(alpha, reduced_motion);
+    draw_cooldown_arcs(gs, alpha);
     for enemy in gs.enemies.iter() {
-        enemy.draw();
+        enemy.draw(alpha, reduced_motion);
     }
     for projectile in gs.projectiles.iter() {
-        projectile.draw();
+        projectile.draw(alpha);
     }
-    draw_text(
+    draw_tethers(gs, alpha);
+    draw_shard_bursts(gs);
+    if gs.debug_colliders {
+        draw_colliders(gs);
+    }
+    let layout = Layout::current();
+    let font_size = layout.s(20.0);
+    let hints = [
         "Auto-battler: Move with Arrow Keys, aim with mouse, weapon fires automatically",
-        20.0,
-        20.0,
-        20.0,
-        DARKGRAY,
-    );
-    draw_text(
-        "Avoid the enemies. Don't leave the Screen! OR DIE!",
-        20.0,
-        40.0,
-        20.0,
-        DARKGRAY,
-    );
-    draw_text("Press 'R' to reload scripts", 20.0, 60.0, 20.0, DARKGRAY);
-    draw_text("Press 'P' to pause", 20.0, 80.0, 20.0, DARKGRAY);
+        match gs.game_constants.bounds_mode {
+            BoundsMode::Deadly => "Avoid the enemies. Don't leave the Screen! OR DIE!",
+            BoundsMode::Wrap => "Avoid the enemies. The screen edges wrap around!",
+        },
+        "Press 'R' to reload scripts",
+        "Press 'P' to pause",
+    ];
+    for (i, hint) in hints.iter().enumerate() {
+        let y = layout.top(20.0 + i as f32 * 20.0);
+        draw_text(hint, layout.left(20.0), y, font_size, DARKGRAY);
+    }
+
+    if gs.time_scale > 1.0 {
+        let ff_text = format!(">> {}x", gs.time_scale);
+        draw_text(
+            &ff_text,
+            layout.left(20.0),
+            layout.top(100.0),
+            font_size,
+            ORANGE,
+        );
+    }
+
+    if gs.player.is_buffed() {
+        let buff_text = format!("Fire rate buff {:.0}s", gs.player.buff_timer.ceil());
+        draw_text(
+            &buff_text,
+            layout.left(20.0),
+            layout.top(120.0),
+            font_size,
+            GREEN,
+        );
+    }
+
+    if gs.sandbox {
+        draw_sandbox_hud(gs, &layout);
+    }
+
     let wave_text = format!("Wave: {}", gs.wave);
-    draw_text(&wave_text, screen_width() - 120.0, 20.0, 20.0, DARKGRAY);
+    let right_x = layout.right(120.0);
+    draw_text(&wave_text, right_x, layout.top(20.0), font_size, DARKGRAY);
 
     // Show player level and XP
     let level_text = format!("Level: {}", gs.player.get_level());
-    draw_text(&level_text, screen_width() - 120.0, 40.0, 20.0, DARKGRAY);
+    draw_text(&level_text, right_x, layout.top(40.0), font_size, DARKGRAY);
 
     let xp_text = format!(
         "XP: {}/{}",
         gs.player.get_xp(),
         gs.player.xp_for_next_level()
     );
-    draw_text(&xp_text, screen_width() - 120.0, 60.0, 16.0, DARKGRAY);
+    draw_text(
+        &xp_text,
+        right_x,
+        layout.top(60.0),
+        layout.s(16.0),
+        DARKGRAY,
+    );
 
-    // Show current weapon info
-    let weapons = gs.player.get_weapons();
-    for (i, weapon) in weapons.iter().enumerate() {
-        let weapon_text = format!("{:?} Lvl{}", weapon.weapon_type, weapon.get_level());
-        draw_text(
-            &weapon_text,
-            screen_width() - 200.0,
-            80.0 + (i as f32 * 20.0),
-            16.0,
-            DARKGRAY,
-        );
+    // Progress within the wave below the weapon row, alive and telegraphed enemies count as remaining
+    let remaining_text = format!(
+        "{}/{} remaining",
+        gs.remaining_enemies(),
+        gs.wave_enemy_total
+    );
+    draw_text(
+        &remaining_text,
+        layout.right(200.0),
+        layout.top(140.0),
+        layout.s(16.0),
+        DARKGRAY,
+    );
+
+    draw_weapon_row(gs, &layout);
+    draw_player_health(gs, &layout);
+
+    for text in gs.floating_texts.iter() {
+        // Rise and fade out over the lifetime
+        let t = (text.time_remaining / FLOATING_TEXT_TIME).clamp(0.0, 1.0);
+        let mut color = text.color;
+        color.a *= t;
+        let y = text.pos.y - 30.0 - (1.0 - t) * 25.0;
+        draw_text(&text.text, text.pos.x + 10.0, y, 22.0, color);
+    }
+
+    if gs.wave_reward_open() && !gs.paused {
+        draw_wave_reward(gs, &layout);
     }
 
-    if gs.paused {
+    if gs.paused && gs.show_stats {
+        draw_stats_tab(gs, &layout);
+    } else if gs.paused {
+        let title_size = layout.s(40.0);
         draw_text(
             "PAUSED",
-            screen_width() / 2.0 - 50.0,
-            screen_height() / 2.0,
-            40.0,
+            layout.centered_text_x("PAUSED", title_size),
+            layout.center_y(),
+            title_size,
             YELLOW,
         );
+        let save_hint = "S: save game    L: load game    Tab: stats";
+        draw_text(
+            save_hint,
+            layout.centered_text_x(save_hint, font_size),
+            layout.center_y() + layout.s(30.0),
+            font_size,
+            YELLOW,
+        );
+        let on_off = |on: bool| if on { "on" } else { "off" };
+        let motion_hint = format!(
+            "M: reduced motion ({})    B: static background ({})",
+            on_off(gs.settings.reduced_motion),
+            on_off(gs.settings.static_background)
+        );
+        draw_text(
+            &motion_hint,
+            layout.centered_text_x(&motion_hint, font_size),
+            layout.center_y() + layout.s(55.0),
+            font_size,
+            YELLOW,
+        );
+    }
+
+    if gs.graze_popup_timer > 0.0 {
+        // Float the popup upwards above the player while it fades out
+        let t = (gs.graze_popup_timer / GRAZE_POPUP_TIME).clamp(0.0, 1.0);
+        let pos = gs.player.pos;
+        let y = pos.y - gs.player.get_radius() - 10.0 - (1.0 - t) * 20.0;
+        draw_text(
+            "GRAZE!",
+            pos.x - 30.0,
+            y,
+            24.0,
+            Color::new(1.0, 0.85, 0.2, t),
+        );
+    }
+
+    gs.console.draw();
+}
+
+/// Sandbox controls and the DPS dealt to the practice dummies
+fn draw_sandbox_hud(gs: &GameState, layout: &Layout) {
+    let font_size = layout.s(20.0);
+    let immunity = if gs.dummy_immune { "on" } else { "off" };
+    let controls = format!(
+        "SANDBOX  N: dummy at cursor  I: dummy immunity ({})",
+        immunity
+    );
+    draw_text(
+        &controls,
+        layout.left(20.0),
+        layout.top(140.0),
+        font_size,
+        SKYBLUE,
+    );
+    let dps_text = format!(
+        "DPS: {:.1} (last {:.0}s)",
+        gs.dummy_damage.dps(gs.run_time),
+        DPS_WINDOW
+    );
+    draw_text(
+        &dps_text,
+        layout.left(20.0),
+        layout.top(160.0),
+        font_size,
+        SKYBLUE,
+    );
+}
+
+/// Quick pick of the reward for the cleared wave, drawn over the empty arena
+fn draw_wave_reward(gs: &GameState, layout: &Layout) {
+    let rewards = gs.game_constants.wave_rewards;
+    let title = format!("WAVE {} CLEARED - pick a reward", gs.wave);
+    let title_size = layout.s(32.0);
+    let y = layout.center_y() - layout.s(60.0);
+    draw_text(
+        &title,
+        layout.centered_text_x(&title, title_size),
+        y,
+        title_size,
+        GREEN,
+    );
+
+    let font_size = layout.s(22.0);
+    let options = [
+        format!("1: Heal {:.0} health", rewards.heal),
+        format!(
+            "2: x{:.1} fire rate for {:.0}s",
+            rewards.buff_multiplier, rewards.buff_duration
+        ),
+        format!("3: +{} XP", rewards.bonus_xp),
+        "Enter: skip".to_owned(),
+    ];
+    for (i, option) in options.iter().enumerate() {
+        draw_text(
+            option,
+            layout.centered_text_x(option, font_size),
+            y + layout.s(40.0 + i as f32 * 28.0),
+            font_size,
+            if i < 3 { WHITE } else { GRAY },
+        );
+    }
+}
+
+/// A line of the stats tab
+enum StatsLine {
+    Header(&'static str),
+    Value(String),
+    Blank,
+}
+
+/// Lines of the stats tab: run, player and one block per weapon
+fn stats_lines(gs: &GameState) -> Vec<StatsLine> {
+    let kills: u32 = gs.kills_by_weapon.values().sum();
+    let minutes = (gs.run_time / 60.0) as u32;
+    let seconds = (gs.run_time % 60.0) as u32;
+    let stats = gs.player.get_stats();
+
+    let mut lines = vec![
+        StatsLine::Header("RUN"),
+        StatsLine::Value(format!("Wave: {}/{}", gs.wave, gs.game_constants.max_waves)),
+        StatsLine::Value(format!("Kills: {}", kills)),
+        StatsLine::Value(format!("Run time: {}:{:02}", minutes, seconds)),
+        StatsLine::Blank,
+        StatsLine::Header("PLAYER"),
+        StatsLine::Value(format!(
+            "Level: {}  XP: {}/{}",
+            gs.player.get_level(),
+            gs.player.get_xp(),
+            gs.player.xp_for_next_level()
+        )),
+        StatsLine::Value(format!(
+            "Health: {:.0}/{:.0}",
+            gs.player.health.max(0.0),
+            stats.max_health
+        )),
+        StatsLine::Value(format!("Speed: {:.1}", stats.max_speed)),
+        StatsLine::Value(format!("Acceleration: {:.2}", stats.acceleration)),
+        StatsLine::Value(format!("Radius: {:.0}", stats.radius)),
+    ];
+
+    for weapon in gs.player.get_weapons() {
+        let weapon_kills = gs.kills_by_weapon.get(&weapon.weapon_type).unwrap_or(&0);
+        lines.push(StatsLine::Blank);
+        lines.push(StatsLine::Value(format!(
+            "{:?} Lvl {}",
+            weapon.weapon_type,
+            weapon.get_level()
+        )));
+        lines.push(StatsLine::Value(format!(
+            "Cooldown: {:.2}s",
+            weapon.stats.cooldown
+        )));
+        lines.push(StatsLine::Value(format!(
+            "Damage: {}",
+            weapon.damage_label()
+        )));
+        lines.push(StatsLine::Value(format!(
+            "Projectiles: {}",
+            weapon.stats.projectile_count
+        )));
+        lines.push(StatsLine::Value(format!("Kills: {}", weapon_kills)));
+        lines.push(StatsLine::Value(format!(
+            "Mastery: {}",
+            weapon.mastery_tier
+        )));
+        if weapon.stats.lifesteal > 0.0 {
+            lines.push(StatsLine::Value(format!(
+                "Lifesteal: {:.0}%",
+                weapon.stats.lifesteal * 100.0
+            )));
+        }
+    }
+    lines
+}
+
+/// Panel of the stats tab, the title takes the first two lines
+fn stats_panel(layout: &Layout) -> Rect {
+    Rect::new(
+        layout.left(100.0),
+        layout.top(110.0),
+        layout.width - layout.s(200.0),
+        layout.height - layout.s(160.0),
+    )
+}
+
+/// Scroll offset in reference pixels at which the last line of the stats tab is visible
+pub fn stats_max_scroll(gs: &GameState, layout: &Layout) -> f32 {
+    let panel = stats_panel(layout);
+    let visible = panel.h - layout.s(22.0 * 2.0 + 8.0);
+    let content = layout.s(stats_lines(gs).len() as f32 * 22.0);
+    (content - visible).max(0.0) / layout.scale
+}
+
+/// Read-only overview of the build on the pause screen, scrolls when it doesn't fit
+fn draw_stats_tab(gs: &GameState, layout: &Layout) {
+    let panel = stats_panel(layout);
+    draw_rectangle(
+        panel.x,
+        panel.y,
+        panel.w,
+        panel.h,
+        Color::new(0.0, 0.0, 0.0, 0.85),
+    );
+    draw_rectangle_lines(panel.x, panel.y, panel.w, panel.h, 2.0, YELLOW);
+
+    let font_size = layout.s(20.0);
+    let line_height = layout.s(22.0);
+    let title = "STATS (Tab: back, wheel/arrows: scroll)";
+    draw_text(
+        title,
+        panel.x + layout.s(16.0),
+        panel.y + line_height,
+        font_size,
+        YELLOW,
+    );
+
+    // Lines outside the panel are skipped, macroquad has no text clipping
+    let top = panel.y + line_height * 2.0;
+    let bottom = panel.bottom() - layout.s(8.0);
+    let lines = stats_lines(gs);
+    // The window may have been resized since the last scroll input
+    let scroll = layout.s(gs.stats_scroll.min(stats_max_scroll(gs, layout)));
+    for (i, line) in lines.iter().enumerate() {
+        let y = top + line_height * (i + 1) as f32 - scroll;
+        if y < top || y > bottom {
+            continue;
+        }
+        let (text, color) = match line {
+            StatsLine::Header(text) => (*text, YELLOW),
+            StatsLine::Value(text) => (text.as_str(), WHITE),
+            StatsLine::Blank => continue,
+        };
+        draw_text(text, panel.x + layout.s(16.0), y, font_size, color);
+    }
+}
+
+/// Faint lines over the arena with their coordinates labeled, for debugging positions
+fn draw_grid() {
+    let line_color = Color::new(1.0, 1.0, 1.0, 0.08);
+    let label_color = Color::new(1.0, 1.0, 1.0, 0.35);
+    let (width, height) = (screen_width(), screen_height());
+
+    let mut i = 0;
+    while i as f32 * GRID_SPACING <= width {
+        let x = i as f32 * GRID_SPACING;
+        draw_line(x, 0.0, x, height, 1.0, line_color);
+        if i % GRID_LABEL_EVERY == 0 {
+            draw_text(&format!("{}", x), x + 2.0, 12.0, 14.0, label_color);
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i as f32 * GRID_SPACING <= height {
+        let y = i as f32 * GRID_SPACING;
+        draw_line(0.0, y, width, y, 1.0, line_color);
+        if i % GRID_LABEL_EVERY == 0 && i > 0 {
+            draw_text(&format!("{}", y), 2.0, y - 2.0, 14.0, label_color);
+        }
+        i += 1;
+    }
+}
+
+/// Outlines every collider at the logic position, to check hitboxes against the visuals
+fn draw_colliders(gs: &GameState) {
+    draw_collider(&gs.player, GREEN);
+    for enemy in gs.enemies.iter() {
+        draw_collider(enemy, RED);
+    }
+    for projectile in gs.projectiles.iter() {
+        draw_collider(projectile, SKYBLUE);
+    }
+    for barrier in gs.barriers.iter() {
+        draw_collider(barrier, WHITE);
+    }
+}
+
+fn draw_collider(entity: &impl Collidable, color: Color) {
+    let pos = entity.position();
+    match entity.collider() {
+        Collider::Circle { radius } => draw_circle_lines(pos.x, pos.y, radius, 1.0, color),
+        Collider::Rect { width, height } => draw_rectangle_lines(
+            pos.x - width / 2.0,
+            pos.y - height / 2.0,
+            width,
+            height,
+            1.0,
+            color,
+        ),
+    }
+}
+
+/// Cracking ring where a Glass enemy died, with one crack per shard about to fly
+fn draw_shard_bursts(gs: &GameState) {
+    let shards = gs.glass_shards;
+    let color = gs.visual_config.glass_enemy.circle_color.to_color();
+    let radius = gs.glass_enemy_stats.radius;
+    for burst in gs.shard_bursts.iter() {
+        // The cracks grow until the shards fly
+        let t = if shards.telegraph_time > 0.0 {
+            1.0 - (burst.time_remaining / shards.telegraph_time).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        draw_circle_lines(burst.pos.x, burst.pos.y, radius, 2.0, color);
+        for i in 0..shards.count {
+            let dir = Vec2::from_angle(i as f32 * std::f32::consts::TAU / shards.count as f32);
+            let end = burst.pos + dir * radius * (1.0 + t);
+            draw_line(burst.pos.x, burst.pos.y, end.x, end.y, 1.5, WHITE);
+        }
+    }
+}
+
+/// Beams from the player to the enemies linked by a tether
+fn draw_tethers(gs: &GameState, alpha: f32) {
+    let player_pos = gs.player.prev_pos.lerp(gs.player.pos, alpha);
+    for weapon in gs.player.get_weapons() {
+        let Some(target_id) = weapon.tether_target else {
+            continue;
+        };
+        let Some(target) = gs.entity(target_id) else {
+            continue;
+        };
+        let enemy_pos = target.interpolated_position(alpha);
+        let color = get_weapon_color(weapon.weapon_type);
+        draw_line(
+            player_pos.x,
+            player_pos.y,
+            enemy_pos.x,
+            enemy_pos.y,
+            4.0,
+            Color::new(color.r, color.g, color.b, 0.35),
+        );
+        draw_line(
+            player_pos.x,
+            player_pos.y,
+            enemy_pos.x,
+            enemy_pos.y,
+            1.5,
+            WHITE,
+        );
+    }
+}
+
+/// One thin arc per weapon around the player that fills up while the weapon recovers and
+/// lights up in the weapon color once it is ready, so the HUD row can stay out of sight
+fn draw_cooldown_arcs(gs: &GameState, alpha: f32) {
+    if !gs.show_cooldown_arcs {
+        return;
+    }
+
+    let pos = gs.player.prev_pos.lerp(gs.player.pos, alpha);
+    let radius = gs.player.get_radius() + COOLDOWN_ARC_OFFSET;
+    let segment = 360.0 / MAX_WEAPON_SLOTS as f32;
+    let span = segment - COOLDOWN_ARC_GAP;
+    for (i, weapon) in gs
+        .player
+        .get_weapons()
+        .iter()
+        .take(MAX_WEAPON_SLOTS)
+        .enumerate()
+    {
+        // Segments start at the top and go clockwise in slot order
+        let start = -90.0 + i as f32 * segment + COOLDOWN_ARC_GAP / 2.0;
+        let (fill, ready) = match weapon.stats.fire_mode {
+            FireMode::Cooldown => (weapon.readiness(), weapon.readiness() >= 1.0),
+            FireMode::Overheat => (1.0 - weapon.heat_fraction(), !weapon.overheated),
+        };
+        let mut color = get_weapon_color(weapon.weapon_type);
+        draw_arc(
+            pos.x,
+            pos.y,
+            16,
+            radius,
+            start,
+            1.0,
+            span,
+            Color::new(color.r, color.g, color.b, 0.15),
+        );
+        color.a = if ready { 0.7 } else { 0.35 };
+        if fill > 0.0 {
+            draw_arc(pos.x, pos.y, 16, radius, start, 2.0, span * fill, color);
+        }
+    }
+}
+
+/// Faint dashed line from the player to the cursor while an aimed weapon is equipped
+fn draw_aim_line(gs: &GameState, alpha: f32) {
+    let has_aimed_weapon = gs
+        .player
+        .get_weapons()
+        .iter()
+        .any(|w| w.weapon_type.is_aimed());
+    if !gs.show_aim_line || !has_aimed_weapon {
+        return;
+    }
+
+    let dash_length = 8.0;
+    let gap_length = 6.0;
+    let color = Color::new(1.0, 1.0, 1.0, 0.15);
+
+    let start = gs.player.prev_pos.lerp(gs.player.pos, alpha);
+    let (mouse_x, mouse_y) = mouse_position();
+    let to_mouse = Vec2::new(mouse_x, mouse_y) - start;
+    let length = to_mouse.length();
+    if length <= gs.player.get_radius() {
+        return;
+    }
+    let dir = to_mouse / length;
+
+    // Start at the edge of the player circle
+    let mut d = gs.player.get_radius();
+    while d < length {
+        let a = start + dir * d;
+        let b = start + dir * (d + dash_length).min(length);
+        draw_line(a.x, a.y, b.x, b.y, 1.5, color);
+        d += dash_length + gap_length;
+    }
+}
+
+/// Health bar at the top center of the screen
+fn draw_player_health(gs: &GameState, layout: &Layout) {
+    let Rect {
+        x,
+        y,
+        w: width,
+        h: height,
+    } = layout.health_bar();
+    let max_health = gs.player.get_max_health();
+    let fraction = if max_health > 0.0 {
+        (gs.player.health / max_health).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    draw_rectangle(x, y, width, height, DARKGRAY);
+    draw_rectangle(x, y, width * fraction, height, RED);
+    let text = format!("HP: {:.0}/{:.0}", gs.player.health.max(0.0), max_health);
+    draw_text(
+        &text,
+        x + width + layout.s(8.0),
+        y + height,
+        layout.s(18.0),
+        DARKGRAY,
+    );
+}
+
+/// Pulsing markers where telegraphed enemies are about to appear
+fn draw_spawn_telegraphs(gs: &GameState) {
+    let telegraph_time = gs.game_constants.spawn_telegraph_time.max(0.001);
+    for pending in gs.pending_spawns.iter() {
+        let visual_config = gs.enemy_visual_config(pending.enemy_type);
+        let radius = gs.enemy_stats(pending.enemy_type).radius;
+
+        // The marker closes in on the spawn point while pulsing
+        let t = (pending.time_remaining / telegraph_time).clamp(0.0, 1.0);
+        let pulse = if gs.settings.reduced_motion {
+            0.5
+        } else {
+            0.5 + 0.5 * (get_time() as f32 * 20.0).sin()
+        };
+        let mut color = visual_config.circle_color.to_color();
+        color.a = 0.4 + 0.5 * pulse;
+
+        draw_circle_lines(pending.pos.x, pending.pos.y, radius * (1.0 + t), 2.0, color);
+        draw_circle(pending.pos.x, pending.pos.y, radius * 0.3, color);
+    }
+}
+
+/// Row of equipped weapon icons with their level and a cooldown bar, the weapon picked with
+/// 'C' is outlined and its details are shown below the row
+fn draw_weapon_row(gs: &GameState, layout: &Layout) {
+    let hud_slot = gs.hud_weapon_slot();
+    for (i, weapon) in gs.player.get_weapons().iter().enumerate() {
+        let Rect {
+            x, y, w: slot_size, ..
+        } = layout.weapon_slot(i);
+        let color = get_weapon_color(weapon.weapon_type);
+
+        if i == hud_slot {
+            draw_rectangle_lines(x, y, slot_size, slot_size, 2.0, color);
+        } else {
+            draw_rectangle_lines(x, y, slot_size, slot_size, 1.0, DARKGRAY);
+        }
+        draw_weapon_icon(
+            x + slot_size / 2.0,
+            y + slot_size / 2.0,
+            weapon.weapon_type,
+            color,
+            0.4 * layout.scale,
+        );
+
+        let level_text = format!("{}", weapon.get_level());
+        draw_text(
+            &level_text,
+            x + layout.s(2.0),
+            y + layout.s(12.0),
+            layout.s(14.0),
+            WHITE,
+        );
+        if weapon.mastery_tier > 0 {
+            let mastery_text = format!("M{}", weapon.mastery_tier);
+            let mastery_size = layout.s(12.0);
+            let mastery_width = measure_text(&mastery_text, None, mastery_size as u16, 1.0).width;
+            draw_text(
+                &mastery_text,
+                x + slot_size - mastery_width - layout.s(2.0),
+                y + layout.s(12.0),
+                mastery_size,
+                GOLD,
+            );
+        }
+
+        let bar_y = y + slot_size + layout.s(2.0);
+        let bar_height = layout.s(4.0);
+        draw_rectangle(x, bar_y, slot_size, bar_height, DARKGRAY);
+        match weapon.stats.fire_mode {
+            FireMode::Cooldown => {
+                // Cooldown bar fills up until the weapon is ready to fire again
+                let readiness = weapon.readiness();
+                let bar_color = if readiness >= 1.0 { color } else { GRAY };
+                draw_rectangle(x, bar_y, slot_size * readiness, bar_height, bar_color);
+            }
+            FireMode::Overheat => {
+                // Heat gauge fills with every shot and turns red while the weapon is locked out
+                let heat = weapon.heat_fraction();
+                let bar_color = if weapon.overheated { RED } else { ORANGE };
+                draw_rectangle(x, bar_y, slot_size * heat, bar_height, bar_color);
+            }
+        }
+    }
+
+    if let Some(weapon) = gs.hud_weapon() {
+        let details = format!(
+            "{:?}  Dmg {}  Cd {:.1}s  Kills {}",
+            weapon.weapon_type,
+            weapon.damage_label(),
+            weapon.stats.cooldown,
+            weapon.kills
+        );
+        draw_text(
+            &details,
+            layout.right(200.0),
+            layout.top(160.0),
+            layout.s(14.0),
+            DARKGRAY,
+        );
     }
 }
 
 fn spawn_wave(gs: &mut GameState, config: WaveConfig) -> Result<(), String> {
     let w = screen_width();
     let h = screen_height();
+    // Every queued enemy adds to the total
+    gs.wave_enemy_total = 0;
+    gs.wave_clear_timer = 0.0;
 
     // Spawn basic enemies
     for _ in 0..config.basic_enemy_count {
         let (x, y) = get_spawn_position(w, h);
-        gs.spawn_enemy(EnemyType::Basic, Vec2::new(x, y))?;
+        gs.queue_enemy_spawn(EnemyType::Basic, Vec2::new(x, y));
     }
 
     // Spawn chaser enemies
     for _ in 0..config.chaser_enemy_count {
         let (x, y) = get_spawn_position(w, h);
-        gs.spawn_enemy(EnemyType::Chaser, Vec2::new(x, y))?;
+        gs.queue_enemy_spawn(EnemyType::Chaser, Vec2::new(x, y));
+    }
+
+    // Spawn phaser enemies
+    for _ in 0..config.phaser_enemy_count {
+        let (x, y) = get_spawn_position(w, h);
+        gs.queue_enemy_spawn(EnemyType::Phaser, Vec2::new(x, y));
+    }
+
+    // Spawn berserker enemies
+    for _ in 0..config.berserker_enemy_count {
+        let (x, y) = get_spawn_position(w, h);
+        gs.queue_enemy_spawn(EnemyType::Berserker, Vec2::new(x, y));
+    }
+
+    // Spawn teleporter enemies
+    for _ in 0..config.teleporter_enemy_count {
+        let (x, y) = get_spawn_position(w, h);
+        gs.queue_enemy_spawn(EnemyType::Teleporter, Vec2::new(x, y));
+    }
+
+    // Spawn builder enemies
+    for _ in 0..config.builder_enemy_count {
+        let (x, y) = get_spawn_position(w, h);
+        gs.queue_enemy_spawn(EnemyType::Builder, Vec2::new(x, y));
+    }
+
+    // Spawn glass enemies
+    for _ in 0..config.glass_enemy_count {
+        let (x, y) = get_spawn_position(w, h);
+        gs.queue_enemy_spawn(EnemyType::Glass, Vec2::new(x, y));
     }
 
     Ok(())
@@ -188,3 +925,28 @@ fn get_spawn_position(w: f32, h: f32) -> (f32, f32) {
     };
     (x, y)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::wrap_position;
+    use crate::player::AntiCampConfig;
+
+    #[test]
+    fn test_zero_size_screen_gives_no_nan() {
+        // A minimized window reports 0x0
+        let bounds = Vec2::ZERO;
+        for _ in 0..20 {
+            let (x, y) = get_spawn_position(bounds.x, bounds.y);
+            assert!(x.is_finite() && y.is_finite());
+        }
+
+        let pos = Vec2::new(-30.0, 45.0);
+        assert_eq!(wrap_position(pos, bounds), pos);
+
+        let anti_camp = AntiCampConfig::default();
+        for spawn in anti_camp.spawn_positions(pos, bounds) {
+            assert!(spawn.is_finite());
+        }
+    }
+}
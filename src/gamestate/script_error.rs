@@ -1,43 +1,47 @@
 use macroquad::prelude::*;
 
 use super::GameState;
+use crate::layout::Layout;
 
 pub fn process(gs: &mut GameState) {
     clear_background(BLACK);
+    let layout = Layout::current();
+    let title_pos = layout.menu(300.0, 360.0);
     draw_text(
         "SCRIPT ERROR",
-        screen_width() / 2.0 - 100.0,
-        screen_height() / 2.0 - 40.0,
-        40.0,
+        title_pos.x,
+        title_pos.y,
+        layout.s(40.0),
         RED,
     );
     if let Some(ref msg) = gs.error_message {
         let lines: Vec<&str> = msg.lines().collect();
         for (i, line) in lines.iter().take(5).enumerate() {
+            // The messages are long, they start at the screen edge instead of the menu square
             draw_text(
                 line,
-                20.0,
-                screen_height() / 2.0 + 20.0 + (i as f32 * 20.0),
-                16.0,
+                layout.left(20.0),
+                layout.menu(0.0, 420.0 + i as f32 * 20.0).y,
+                layout.s(16.0),
                 DARKGRAY,
             );
         }
     }
-    draw_text(
-        "Fix scripts/main.roto and press 'R' to reload",
-        screen_width() / 2.0 - 150.0,
-        screen_height() / 2.0 + 120.0,
-        20.0,
-        DARKGRAY,
+    let fix_text = format!(
+        "Fix {} and press 'R' to reload",
+        gs.roto_manager.script_path()
     );
+    let fix_pos = layout.menu(250.0, 520.0);
+    draw_text(&fix_text, fix_pos.x, fix_pos.y, layout.s(20.0), DARKGRAY);
+    let restart_pos = layout.menu(280.0, 550.0);
     draw_text(
         "Or press Return to Restart",
-        screen_width() / 2.0 - 120.0,
-        screen_height() / 2.0 + 150.0,
-        20.0,
+        restart_pos.x,
+        restart_pos.y,
+        layout.s(20.0),
         DARKGRAY,
     );
     if is_key_pressed(KeyCode::Enter) {
-        *gs = GameState::new(gs.assets.clone());
+        gs.restart();
     }
 }
@@ -1,7 +1,11 @@
 use macroquad::prelude::*;
 
 use super::GameState;
-use crate::weapon::{WeaponStats, WeaponType};
+use crate::layout::Layout;
+use crate::weapon::{MAX_WEAPON_SLOTS, Weapon, WeaponStats, WeaponType};
+
+/// Seconds one loop of the animated card previews takes
+const PREVIEW_LOOP_TIME: f64 = 1.5;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum WeaponSelectionContext {
@@ -10,10 +14,15 @@ pub enum WeaponSelectionContext {
 }
 
 pub fn process(gs: &mut GameState) {
-    // Keys 1-3 always correspond to the three weapon types in order
+    // Keys 1-7 always correspond to the seven weapon types in order
     // Key 1: EnergyBall - add if don't have, upgrade if have
     // Key 2: Pulse - add if don't have, upgrade if have
     // Key 3: HomingMissile - add if don't have, upgrade if have
+    // Key 4: Cannon - add if don't have, upgrade if have
+    // Key 5: GravityWell - add if don't have, upgrade if have
+    // Key 6: Adaptive - add if don't have, upgrade if have
+    // Key 7: Tether - add if don't have, upgrade if have
+    // With all slots taken, Shift + key replaces the lowest level weapon
 
     if is_key_pressed(KeyCode::Key1) {
         handle_weapon_selection(gs, WeaponType::EnergyBall);
@@ -21,6 +30,14 @@ pub fn process(gs: &mut GameState) {
         handle_weapon_selection(gs, WeaponType::Pulse);
     } else if is_key_pressed(KeyCode::Key3) {
         handle_weapon_selection(gs, WeaponType::HomingMissile);
+    } else if is_key_pressed(KeyCode::Key4) {
+        handle_weapon_selection(gs, WeaponType::Cannon);
+    } else if is_key_pressed(KeyCode::Key5) {
+        handle_weapon_selection(gs, WeaponType::GravityWell);
+    } else if is_key_pressed(KeyCode::Key6) {
+        handle_weapon_selection(gs, WeaponType::Adaptive);
+    } else if is_key_pressed(KeyCode::Key7) {
+        handle_weapon_selection(gs, WeaponType::Tether);
     }
 
     if gs.num_lvlups == 0 {
@@ -34,11 +51,23 @@ fn handle_weapon_selection(gs: &mut GameState, weapon_type: WeaponType) {
     // Find if player already has this weapon type
     if let Some(index) = weapons.iter().position(|w| w.weapon_type == weapon_type) {
         // Player has this weapon - upgrade it
-        gs.player.level_up_weapon(index);
+        gs.player.level_up_weapon(index, &gs.weapon_tuning);
     } else {
         // Player doesn't have this weapon - add it (if room available)
-        if weapons.len() < 3 {
-            gs.player.add_weapon(weapon_type);
+        if weapons.len() >= MAX_WEAPON_SLOTS {
+            let replace = is_key_down(KeyCode::LeftShift) || is_key_down(KeyCode::RightShift);
+            let lowest = weapons
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, w)| w.get_level())
+                .map(|(i, _)| i);
+            match lowest {
+                Some(index) if replace => gs.replace_weapon(index, weapon_type),
+                // No free slot, don't spend the level up
+                _ => return,
+            }
+        } else {
+            gs.player.add_weapon(weapon_type, &gs.weapon_tuning);
         }
     }
 
@@ -73,41 +102,43 @@ pub fn draw(gs: &GameState) {
 }
 
 fn draw_weapon_selection(gs: &GameState, context: WeaponSelectionContext) {
+    let layout = Layout::current();
+
     // Draw title
     let lvl_str = format!("{} LEVEL UP(s) - SELECT OUR MAGIC!", gs.num_lvlups);
     let title = match context {
         WeaponSelectionContext::InitialSelection => "SELECT OUR MAGIC!",
         WeaponSelectionContext::LevelUp => lvl_str.as_str(),
     };
-    let title_size = 40.0;
-    let title_width = measure_text(title, None, title_size as u16, 1.0).width;
+    let title_size = layout.s(40.0);
     draw_text(
         title,
-        screen_width() / 2.0 - title_width / 2.0,
-        450.0,
+        layout.centered_text_x(title, title_size),
+        layout.menu(0.0, 410.0).y,
         title_size,
         YELLOW,
     );
 
     // Draw weapon cards
-    let card_width = 200.0;
-    let card_height = 280.0;
-    let card_spacing = 40.0;
-    let card_y = 480.0;
-    let total_width = card_width * 3.0 + card_spacing * 2.0;
-    let start_x = (screen_width() - total_width) / 2.0;
-
     let all_weapon_types = [
         WeaponType::EnergyBall,
         WeaponType::Pulse,
         WeaponType::HomingMissile,
+        WeaponType::Cannon,
+        WeaponType::GravityWell,
+        WeaponType::Adaptive,
+        WeaponType::Tether,
     ];
 
+    let cards = layout.card_rects(all_weapon_types.len());
+    let cards_bottom = cards.first().map_or(0.0, |card| card.bottom());
+
     let weapons = gs.player.get_weapons();
+    // The previews loop forever, with reduced motion the cards show the static icons
+    let animate = !gs.settings.reduced_motion;
 
-    // Draw all three weapon types
-    for (i, weapon_type) in all_weapon_types.iter().enumerate() {
-        let x = start_x + (card_width + card_spacing) * i as f32;
+    // Draw all weapon types
+    for (i, (weapon_type, card)) in all_weapon_types.iter().zip(cards.iter()).enumerate() {
         let key = format!("{}", i + 1);
         let name = format!("{:?}", weapon_type);
         let color = get_weapon_color(*weapon_type);
@@ -115,50 +146,44 @@ fn draw_weapon_selection(gs: &GameState, context: WeaponSelectionContext) {
         // Check if player has this weapon
         if let Some(weapon) = weapons.iter().find(|w| w.weapon_type == *weapon_type) {
             // Player has this weapon - show upgrade card
-            draw_level_up_card(
-                x,
-                card_y,
-                card_width,
-                card_height,
-                &key,
-                &name,
-                weapon,
-                color,
-            );
+            draw_level_up_card(&layout, *card, &key, &name, weapon, color, animate);
         } else {
             // Player doesn't have this weapon - show new weapon card
-            let stats = WeaponStats::from(*weapon_type);
+            // Describe the weapon as the player would get it, with the script's tuning
+            let mut preview = Weapon::new(*weapon_type);
+            preview.apply_tuning(&gs.weapon_tuning);
 
             // Always show flavor text
             let flavor_text = match weapon_type {
                 WeaponType::EnergyBall => "Fast projectile that\ntravels straight. You AIM!",
                 WeaponType::Pulse => "Area attack that\nexpands from player.",
                 WeaponType::HomingMissile => "Seeks nearest enemy\nand follows them.",
+                WeaponType::Cannon => "Hold SPACE to charge,\nrelease to fire. You AIM!",
+                WeaponType::GravityWell => "Places a well that\npulls enemies in.\nYou AIM!",
+                WeaponType::Adaptive => "Single shot, a ring\nwhen surrounded.\nYou AIM!",
+                WeaponType::Tether => "Beam that drains and\nslows the nearest enemy.",
             };
 
-            let desc = generate_weapon_description(*weapon_type, &stats, flavor_text);
-            draw_weapon_card(
-                x,
-                card_y,
-                card_width,
-                card_height,
-                &key,
-                &name,
-                &desc,
-                color,
-            );
+            let desc = if weapons.len() >= MAX_WEAPON_SLOTS {
+                format!(
+                    "{}\n\nNo free slot!\nShift+{} replaces your\nlowest level weapon",
+                    flavor_text, key
+                )
+            } else {
+                generate_weapon_description(*weapon_type, &preview.stats, flavor_text)
+            };
+            draw_weapon_card(&layout, *card, &key, *weapon_type, &desc, color, animate);
         }
     }
 
     // Draw level up subtitle below cards
     if context == WeaponSelectionContext::LevelUp {
         let subtitle = format!("Level {} - Choose an Upgrade", gs.player.get_level());
-        let subtitle_size = 24.0;
-        let subtitle_width = measure_text(&subtitle, None, subtitle_size as u16, 1.0).width;
+        let subtitle_size = layout.s(24.0);
         draw_text(
             &subtitle,
-            screen_width() / 2.0 - subtitle_width / 2.0,
-            card_y + card_height + 30.0,
+            layout.centered_text_x(&subtitle, subtitle_size),
+            cards_bottom + layout.s(30.0),
             subtitle_size,
             YELLOW,
         );
@@ -166,99 +191,124 @@ fn draw_weapon_selection(gs: &GameState, context: WeaponSelectionContext) {
 
     // Draw instruction
     let (instruction, instruction_size) = match context {
-        WeaponSelectionContext::InitialSelection => ("Press 1, 2, or 3 to select", 24.0),
-        WeaponSelectionContext::LevelUp => ("Press 1-3 to upgrade or acquire weapon", 20.0),
+        WeaponSelectionContext::InitialSelection => ("Press 1-7 to select", 24.0),
+        WeaponSelectionContext::LevelUp => ("Press 1-7 to upgrade or acquire weapon", 20.0),
     };
-    let instruction_width = measure_text(instruction, None, instruction_size as u16, 1.0).width;
+    let instruction_size = layout.s(instruction_size);
     draw_text(
         instruction,
-        screen_width() / 2.0 - instruction_width / 2.0,
-        card_y + card_height + 60.0,
+        layout.centered_text_x(instruction, instruction_size),
+        cards_bottom + layout.s(60.0),
         instruction_size,
         LIGHTGRAY,
     );
 }
 
-fn get_weapon_color(weapon_type: WeaponType) -> Color {
+pub fn get_weapon_color(weapon_type: WeaponType) -> Color {
     match weapon_type {
         WeaponType::EnergyBall => BLUE,
         WeaponType::Pulse => GREEN,
         WeaponType::HomingMissile => RED,
+        WeaponType::Cannon => ORANGE,
+        WeaponType::GravityWell => VIOLET,
+        WeaponType::Adaptive => PINK,
+        WeaponType::Tether => LIME,
     }
 }
 
 fn draw_level_up_card(
-    x: f32,
-    y: f32,
-    width: f32,
-    height: f32,
+    layout: &Layout,
+    card: Rect,
     key: &str,
     name: &str,
     weapon: &crate::weapon::Weapon,
     color: Color,
+    animate: bool,
 ) {
+    let Rect {
+        x,
+        y,
+        w: width,
+        h: height,
+    } = card;
+    let center_x = x + width / 2.0;
+    let fit = layout.card_text_scale(card);
+
     // Draw card background
     draw_rectangle(x, y, width, height, Color::new(0.2, 0.3, 0.2, 0.95));
 
     // Draw card border (thicker for level up)
-    draw_rectangle_lines(x, y, width, height, 4.0, GOLD);
+    draw_rectangle_lines(x, y, width, height, layout.s(4.0), GOLD);
 
     // Draw key indicator
     let key_text = format!("[{}]", key);
-    let key_size = 28.0;
+    let key_size = layout.s(28.0) * fit;
     let key_width = measure_text(&key_text, None, key_size as u16, 1.0).width;
     draw_text(
         &key_text,
-        x + width / 2.0 - key_width / 2.0,
-        y + 35.0,
+        center_x - key_width / 2.0,
+        y + layout.s(35.0),
         key_size,
         GOLD,
     );
 
-    // Draw weapon icon
-    let icon_y = y + 60.0;
-    draw_weapon_icon(x + width / 2.0, icon_y, name, color);
+    // Draw weapon preview or icon
+    if animate {
+        let preview = layout.card_preview(card);
+        draw_weapon_preview(preview, weapon.weapon_type, color, layout.scale * fit);
+    } else {
+        let icon_y = y + layout.s(60.0);
+        draw_weapon_icon(
+            center_x,
+            icon_y,
+            weapon.weapon_type,
+            color,
+            layout.scale * fit,
+        );
+    }
 
     // Draw weapon name and current level
     let name_text = format!("{} Lvl{}", name, weapon.get_level());
-    let name_size = 18.0;
+    let name_size = layout.s(18.0) * fit;
     let name_width = measure_text(&name_text, None, name_size as u16, 1.0).width;
     draw_text(
         &name_text,
-        x + width / 2.0 - name_width / 2.0,
-        y + 120.0,
+        center_x - name_width / 2.0,
+        y + layout.s(120.0),
         name_size,
         WHITE,
     );
 
     // Draw "UPGRADE" text
     let upgrade_text = "UPGRADE";
-    let upgrade_size = 20.0;
+    let upgrade_size = layout.s(20.0) * fit;
     let upgrade_width = measure_text(upgrade_text, None, upgrade_size as u16, 1.0).width;
     draw_text(
         upgrade_text,
-        x + width / 2.0 - upgrade_width / 2.0,
-        y + 150.0,
+        center_x - upgrade_width / 2.0,
+        y + layout.s(150.0),
         upgrade_size,
         GOLD,
     );
 
     // Draw current stats preview
     let stats_text = format!(
-        "Cooldown: {:.1}s\nDamage: {}\nLevel: {} → {}",
+        "Cooldown: {:.1}s\nDamage: {}\nLevel: {} → {}\nMastery: {} ({} kills)",
         weapon.stats.cooldown,
-        weapon.stats.projectile_stats.damage as i32,
+        weapon.damage_label(),
         weapon.get_level(),
-        weapon.get_level() + 1
+        weapon.get_level() + 1,
+        weapon.mastery_tier,
+        weapon.kills
     );
-    let stats_size = 13.0;
-    let stats_y_start = y + 175.0;
+    let stats_size = layout.s(13.0) * fit;
+    let stats_y_start = y + layout.s(175.0);
     for (i, line) in stats_text.lines().enumerate() {
         let line_width = measure_text(line, None, stats_size as u16, 1.0).width;
         draw_text(
             line,
-            x + width / 2.0 - line_width / 2.0,
-            stats_y_start + (i as f32 * 16.0),
+            center_x - line_width / 2.0,
+            stats_y_start + layout.s(i as f32 * 16.0),
             stats_size,
             LIGHTGRAY,
         );
@@ -274,7 +324,10 @@ fn generate_weapon_description(
 
     // Calculate range based on projectile type
     let range = match weapon_type {
-        WeaponType::EnergyBall | WeaponType::HomingMissile => {
+        WeaponType::EnergyBall
+        | WeaponType::HomingMissile
+        | WeaponType::Cannon
+        | WeaponType::Adaptive => {
             let distance = projectile_stats.speed * projectile_stats.time_to_live;
             if distance > 500.0 {
                 "Long"
@@ -284,6 +337,24 @@ fn generate_weapon_description(
                 "Short"
             }
         }
+        WeaponType::GravityWell => {
+            if projectile_stats.radius > 150.0 {
+                "Large"
+            } else if projectile_stats.radius > 90.0 {
+                "Medium"
+            } else {
+                "Small"
+            }
+        }
+        WeaponType::Tether => {
+            if stats.tether.range > 300.0 {
+                "Long"
+            } else if stats.tether.range > 180.0 {
+                "Medium"
+            } else {
+                "Short"
+            }
+        }
         WeaponType::Pulse => {
             let size = projectile_stats.width.max(projectile_stats.height);
             if size > 150.0 {
@@ -296,10 +367,17 @@ fn generate_weapon_description(
         }
     };
 
-    // Categorize damage
-    let damage_level = if projectile_stats.damage >= 20.0 {
+    // Categorize damage, per second for the tether
+    let damage = if weapon_type == WeaponType::Tether {
+        stats.tether.dps
+    } else {
+        projectile_stats.damage
+    };
+    let damage_level = if damage <= 0.0 {
+        "None"
+    } else if damage >= 20.0 {
         "High"
-    } else if projectile_stats.damage >= 12.0 {
+    } else if damage >= 12.0 {
         "Medium"
     } else {
         "Low"
@@ -307,109 +385,256 @@ fn generate_weapon_description(
 
     format!(
         "{}\n\nCooldown: {:.1}s\nDamage: {} ({})\nRange: {}",
-        flavor_text, stats.cooldown, damage_level, projectile_stats.damage as i32, range
+        flavor_text, stats.cooldown, damage_level, damage as i32, range
     )
 }
 
 fn draw_weapon_card(
-    x: f32,
-    y: f32,
-    width: f32,
-    height: f32,
+    layout: &Layout,
+    card: Rect,
     key: &str,
-    name: &str,
+    weapon_type: WeaponType,
     description: &str,
     color: Color,
+    animate: bool,
 ) {
+    let name = format!("{:?}", weapon_type);
+    let Rect {
+        x,
+        y,
+        w: width,
+        h: height,
+    } = card;
+    let center_x = x + width / 2.0;
+    let fit = layout.card_text_scale(card);
+
     // Draw card background
     draw_rectangle(x, y, width, height, Color::new(0.2, 0.2, 0.2, 0.95));
 
     // Draw card border
-    draw_rectangle_lines(x, y, width, height, 3.0, color);
+    draw_rectangle_lines(x, y, width, height, layout.s(3.0), color);
 
     // Draw key indicator at top
     let key_text = format!("[{}]", key);
-    let key_size = 32.0;
+    let key_size = layout.s(32.0) * fit;
     let key_width = measure_text(&key_text, None, key_size as u16, 1.0).width;
     draw_text(
         &key_text,
-        x + width / 2.0 - key_width / 2.0,
-        y + 40.0,
+        center_x - key_width / 2.0,
+        y + layout.s(40.0),
         key_size,
         color,
     );
 
-    // Draw weapon icon (simple geometric representation)
-    let icon_y = y + 70.0;
-    draw_weapon_icon(x + width / 2.0, icon_y, name, color);
+    // Draw weapon preview or icon (simple geometric representation)
+    if animate {
+        let preview = layout.card_preview(card);
+        draw_weapon_preview(preview, weapon_type, color, layout.scale * fit);
+    } else {
+        let icon_y = y + layout.s(70.0);
+        draw_weapon_icon(center_x, icon_y, weapon_type, color, layout.scale * fit);
+    }
 
     // Draw weapon name
-    let name_size = 22.0;
-    let name_width = measure_text(name, None, name_size as u16, 1.0).width;
+    let name_size = layout.s(22.0) * fit;
+    let name_width = measure_text(&name, None, name_size as u16, 1.0).width;
     draw_text(
-        name,
-        x + width / 2.0 - name_width / 2.0,
-        y + 140.0,
+        &name,
+        center_x - name_width / 2.0,
+        y + layout.s(140.0),
         name_size,
         WHITE,
     );
 
     // Draw description (multi-line)
-    let desc_size = 14.0;
-    let desc_y_start = y + 170.0;
+    let desc_size = layout.s(14.0) * fit;
+    let desc_y_start = y + layout.s(170.0);
     let lines: Vec<&str> = description.lines().collect();
     for (i, line) in lines.iter().enumerate() {
         let line_width = measure_text(line, None, desc_size as u16, 1.0).width;
         draw_text(
             line,
-            x + width / 2.0 - line_width / 2.0,
-            desc_y_start + (i as f32 * 18.0),
+            center_x - line_width / 2.0,
+            desc_y_start + layout.s(i as f32 * 18.0),
             desc_size,
             LIGHTGRAY,
         );
     }
 }
 
-fn draw_weapon_icon(center_x: f32, center_y: f32, weapon_name: &str, color: Color) {
-    match weapon_name {
-        "Energy Ball" => {
+/// Looping mini simulation of the weapon inside `stage`, the player is yellow and the enemies
+/// red like in the game. Everything is placed relative to the stage so it never leaves it.
+fn draw_weapon_preview(stage: Rect, weapon_type: WeaponType, color: Color, scale: f32) {
+    let t = (get_time() / PREVIEW_LOOP_TIME).fract() as f32;
+    draw_rectangle(
+        stage.x,
+        stage.y,
+        stage.w,
+        stage.h,
+        Color::new(0.0, 0.0, 0.0, 0.4),
+    );
+
+    let body = 6.0 * scale;
+    let center = stage.center();
+    let left = Vec2::new(stage.x + body * 2.0, center.y);
+    let right = Vec2::new(stage.right() - body * 2.0, center.y);
+    let thickness = (2.0 * scale).max(1.0);
+    let draw_body = |pos: Vec2, body_color: Color| draw_circle(pos.x, pos.y, body, body_color);
+
+    match weapon_type {
+        WeaponType::EnergyBall => {
+            // A ball flies straight at the enemy, which flashes when hit
+            let travel = (t / 0.7).min(1.0);
+            draw_body(left, YELLOW);
+            draw_body(right, if travel >= 1.0 { WHITE } else { RED });
+            if travel < 1.0 {
+                let pos = left.lerp(right, travel);
+                draw_circle(pos.x, pos.y, body * 0.6, color);
+            }
+        }
+        WeaponType::Pulse => {
+            // A square grows from the player and fades out
+            let half = t * (stage.h / 2.0 - thickness);
+            let mut pulse_color = color;
+            pulse_color.a = 1.0 - t;
+            draw_rectangle_lines(
+                center.x - half,
+                center.y - half,
+                half * 2.0,
+                half * 2.0,
+                thickness,
+                pulse_color,
+            );
+            draw_body(center, YELLOW);
+        }
+        WeaponType::HomingMissile => {
+            // The missile starts upwards and curves onto the enemy, which moves up and down
+            let target = Vec2::new(right.x, center.y + (t * std::f32::consts::TAU).sin() * body);
+            let control = Vec2::new(center.x, stage.y + body);
+            let bezier = |s: f32| left.lerp(control, s).lerp(control.lerp(target, s), s);
+            draw_body(left, YELLOW);
+            draw_body(target, RED);
+            for trail in 0..3 {
+                let s = t - trail as f32 * 0.06;
+                if s > 0.0 {
+                    let pos = bezier(s);
+                    draw_circle(pos.x, pos.y, body * (0.5 - trail as f32 * 0.12), color);
+                }
+            }
+        }
+        WeaponType::Cannon => {
+            // Charges up during the first half, then fires one big shot
+            draw_body(left, YELLOW);
+            draw_body(right, RED);
+            if t < 0.5 {
+                let charge = t / 0.5;
+                let bar_y = left.y + body * 1.5;
+                draw_rectangle(left.x - body, bar_y, body * 2.0 * charge, thickness, color);
+            } else {
+                let pos = left.lerp(right, (t - 0.5) / 0.5);
+                draw_circle(pos.x, pos.y, body * 0.9, color);
+            }
+        }
+        WeaponType::GravityWell => {
+            // Two enemies drift into the well in the middle
+            let reach = stage.h / 2.0 - thickness;
+            draw_circle_lines(center.x, center.y, reach, thickness, color);
+            let angle = t * std::f32::consts::TAU;
+            let swirl = Vec2::from_angle(angle) * reach * 0.4;
+            draw_line(
+                center.x - swirl.x,
+                center.y - swirl.y,
+                center.x + swirl.x,
+                center.y + swirl.y,
+                thickness,
+                color,
+            );
+            let pull = t * t;
+            let offset = Vec2::new(reach - body, 0.0);
+            draw_body((center - offset).lerp(center, pull * 0.8), RED);
+            draw_body((center + offset).lerp(center, pull * 0.8), RED);
+        }
+        WeaponType::Adaptive => {
+            // A single shot first, then a ring once enemies crowd in
+            draw_body(center, YELLOW);
+            if t < 0.5 {
+                let pos = center.lerp(right, t / 0.5);
+                draw_circle(pos.x, pos.y, body * 0.5, color);
+            } else {
+                let distance = (t - 0.5) / 0.5 * (stage.h / 2.0 - body * 0.5);
+                for i in 0..8 {
+                    let pos = center
+                        + Vec2::from_angle(i as f32 * std::f32::consts::FRAC_PI_4) * distance;
+                    draw_circle(pos.x, pos.y, body * 0.4, color);
+                }
+            }
+        }
+        WeaponType::Tether => {
+            // A beam that follows the enemy wherever it goes
+            let target = Vec2::new(
+                right.x,
+                center.y + (t * std::f32::consts::TAU).sin() * (stage.h / 2.0 - body * 1.5),
+            );
+            let width = thickness * (1.5 + (t * std::f32::consts::TAU * 3.0).sin() * 0.5);
+            draw_line(left.x, left.y, target.x, target.y, width * 2.0, color);
+            draw_line(left.x, left.y, target.x, target.y, width * 0.5, WHITE);
+            draw_body(left, YELLOW);
+            draw_body(target, RED);
+        }
+    }
+}
+
+/// Draws a simple geometric icon for the weapon, `scale` 1.0 is the size used on the cards
+pub fn draw_weapon_icon(
+    center_x: f32,
+    center_y: f32,
+    weapon_type: WeaponType,
+    color: Color,
+    scale: f32,
+) {
+    let thickness = (3.0 * scale).max(1.0);
+    match weapon_type {
+        WeaponType::EnergyBall => {
             // Draw a glowing circle with rays
-            draw_circle(center_x, center_y, 25.0, color);
-            draw_circle(center_x, center_y, 20.0, WHITE);
+            draw_circle(center_x, center_y, 25.0 * scale, color);
+            draw_circle(center_x, center_y, 20.0 * scale, WHITE);
             // Draw rays
             for i in 0..8 {
                 let angle = (i as f32) * std::f32::consts::PI / 4.0;
-                let ray_length = 15.0;
-                let x1 = center_x + angle.cos() * 25.0;
-                let y1 = center_y + angle.sin() * 25.0;
-                let x2 = center_x + angle.cos() * (25.0 + ray_length);
-                let y2 = center_y + angle.sin() * (25.0 + ray_length);
-                draw_line(x1, y1, x2, y2, 3.0, color);
+                let ray_length = 15.0 * scale;
+                let inner = 25.0 * scale;
+                let x1 = center_x + angle.cos() * inner;
+                let y1 = center_y + angle.sin() * inner;
+                let x2 = center_x + angle.cos() * (inner + ray_length);
+                let y2 = center_y + angle.sin() * (inner + ray_length);
+                draw_line(x1, y1, x2, y2, thickness, color);
             }
         }
-        "Pulse" => {
+        WeaponType::Pulse => {
             // Draw concentric circles representing expanding wave
-            draw_circle_lines(center_x, center_y, 35.0, 3.0, color);
+            draw_circle_lines(center_x, center_y, 35.0 * scale, thickness, color);
             draw_circle_lines(
                 center_x,
                 center_y,
-                25.0,
-                3.0,
+                25.0 * scale,
+                thickness,
                 Color::new(color.r, color.g, color.b, 0.7),
             );
             draw_circle_lines(
                 center_x,
                 center_y,
-                15.0,
-                3.0,
+                15.0 * scale,
+                thickness,
                 Color::new(color.r, color.g, color.b, 0.4),
             );
-            draw_circle(center_x, center_y, 8.0, WHITE);
+            draw_circle(center_x, center_y, 8.0 * scale, WHITE);
         }
-        "Homing Missile" => {
+        WeaponType::HomingMissile => {
             // Draw a missile shape with trail
-            let missile_length = 40.0;
-            let missile_width = 12.0;
+            let missile_length = 40.0 * scale;
+            let missile_width = 12.0 * scale;
+            let fin_length = 10.0 * scale;
+            let fin_width = 8.0 * scale;
 
             // Missile body (pointing right)
             draw_triangle(
@@ -432,8 +657,8 @@ fn draw_weapon_icon(center_x: f32, center_y: f32, weapon_name: &str, color: Colo
                     center_y - missile_width / 2.0,
                 ),
                 Vec2::new(
-                    center_x - missile_length / 2.0 - 10.0,
-                    center_y - missile_width / 2.0 - 8.0,
+                    center_x - missile_length / 2.0 - fin_length,
+                    center_y - missile_width / 2.0 - fin_width,
                 ),
                 Vec2::new(center_x - missile_length / 2.0, center_y),
                 RED,
@@ -444,19 +669,74 @@ fn draw_weapon_icon(center_x: f32, center_y: f32, weapon_name: &str, color: Colo
                     center_y + missile_width / 2.0,
                 ),
                 Vec2::new(
-                    center_x - missile_length / 2.0 - 10.0,
-                    center_y + missile_width / 2.0 + 8.0,
+                    center_x - missile_length / 2.0 - fin_length,
+                    center_y + missile_width / 2.0 + fin_width,
                 ),
                 Vec2::new(center_x - missile_length / 2.0, center_y),
                 RED,
             );
 
             // Highlight
-            draw_circle(center_x + 5.0, center_y, 4.0, WHITE);
+            draw_circle(center_x + 5.0 * scale, center_y, 4.0 * scale, WHITE);
+        }
+        WeaponType::Cannon => {
+            // Draw a heavy ball with a barrel pointing right
+            draw_rectangle(
+                center_x,
+                center_y - 6.0 * scale,
+                30.0 * scale,
+                12.0 * scale,
+                GRAY,
+            );
+            draw_circle(center_x, center_y, 20.0 * scale, color);
+            draw_circle(
+                center_x - 6.0 * scale,
+                center_y - 6.0 * scale,
+                5.0 * scale,
+                WHITE,
+            );
+        }
+        WeaponType::GravityWell => {
+            // Draw three spiral arms turning into a white core
+            for arm in 0..3 {
+                let arm_angle = arm as f32 * std::f32::consts::TAU / 3.0;
+                for step in 0..6 {
+                    let t = step as f32 / 6.0;
+                    let angle = arm_angle + t * std::f32::consts::PI;
+                    let radius = 35.0 * scale * (1.0 - t);
+                    draw_circle(
+                        center_x + angle.cos() * radius,
+                        center_y + angle.sin() * radius,
+                        (2.0 + 3.0 * t) * scale,
+                        color,
+                    );
+                }
+            }
+            draw_circle(center_x, center_y, 8.0 * scale, WHITE);
+        }
+        WeaponType::Adaptive => {
+            // Draw a big center shot surrounded by a ring of small ones
+            draw_circle(center_x, center_y, 12.0 * scale, color);
+            for i in 0..8 {
+                let angle = i as f32 * std::f32::consts::TAU / 8.0;
+                draw_circle(
+                    center_x + angle.cos() * 30.0 * scale,
+                    center_y + angle.sin() * 30.0 * scale,
+                    5.0 * scale,
+                    color,
+                );
+            }
+            draw_circle(center_x, center_y, 4.0 * scale, WHITE);
         }
-        _ => {
-            // Fallback icon
-            draw_circle(center_x, center_y, 20.0, color);
+        WeaponType::Tether => {
+            // Draw a beam from a small emitter to a ringed target
+            let start = Vec2::new(center_x - 25.0 * scale, center_y + 15.0 * scale);
+            let end = Vec2::new(center_x + 22.0 * scale, center_y - 15.0 * scale);
+            draw_line(start.x, start.y, end.x, end.y, 6.0 * scale, color);
+            draw_line(start.x, start.y, end.x, end.y, 2.0 * scale, WHITE);
+            draw_circle(start.x, start.y, 7.0 * scale, color);
+            draw_circle_lines(end.x, end.y, 12.0 * scale, 2.0 * scale, color);
+            draw_circle(end.x, end.y, 5.0 * scale, WHITE);
         }
     }
 }
@@ -1,86 +1,93 @@
 use macroquad::prelude::*;
 
 use super::GameState;
+use crate::layout::Layout;
 
 pub fn process(gs: &mut GameState) {
     clear_background(BLACK);
 
     super::draw_elf_message(gs);
 
+    let layout = Layout::current();
+
     // Draw victory message
-    draw_text(
-        "VICTORY!",
-        screen_width() / 2.0 - 100.0,
-        screen_height() / 2.0 + 80.0,
-        60.0,
-        GOLD,
-    );
+    let title_pos = layout.menu(300.0, 480.0);
+    draw_text("VICTORY!", title_pos.x, title_pos.y, layout.s(60.0), GOLD);
 
     // Draw congratulations message
     let congrats_text = format!("You survived all {} waves!", gs.game_constants.max_waves);
-    let congrats_width = measure_text(&congrats_text, None, 24, 1.0).width;
+    let congrats_size = layout.s(24.0);
     draw_text(
         &congrats_text,
-        screen_width() / 2.0 - congrats_width / 2.0,
-        screen_height() / 2.0 + 140.0,
-        24.0,
+        layout.centered_text_x(&congrats_text, congrats_size),
+        layout.menu(0.0, 540.0).y,
+        congrats_size,
         YELLOW,
     );
 
     // Draw final stats
     let level_text = format!("Final Level: {}", gs.player.get_level());
+    let level_pos = layout.menu(320.0, 570.0);
     draw_text(
         &level_text,
-        screen_width() / 2.0 - 80.0,
-        screen_height() / 2.0 + 170.0,
-        20.0,
+        level_pos.x,
+        level_pos.y,
+        layout.s(20.0),
         LIGHTGRAY,
     );
 
     let xp_text = format!("Total XP: {}", gs.player.get_xp());
-    draw_text(
-        &xp_text,
-        screen_width() / 2.0 - 70.0,
-        screen_height() / 2.0 + 200.0,
-        20.0,
-        LIGHTGRAY,
-    );
+    let xp_pos = layout.menu(330.0, 600.0);
+    draw_text(&xp_text, xp_pos.x, xp_pos.y, layout.s(20.0), LIGHTGRAY);
 
     // Draw weapon summary
     let weapons = gs.player.get_weapons();
     if !weapons.is_empty() {
+        let header_pos = layout.menu(350.0, 640.0);
         draw_text(
             "Weapons:",
-            screen_width() / 2.0 - 50.0,
-            screen_height() / 2.0 + 240.0,
-            18.0,
+            header_pos.x,
+            header_pos.y,
+            layout.s(18.0),
             LIGHTGRAY,
         );
 
         for (i, weapon) in weapons.iter().enumerate() {
             let weapon_text = format!("{:?} Lvl{}", weapon.weapon_type, weapon.get_level());
+            let weapon_pos = layout.menu(340.0, 665.0 + i as f32 * 22.0);
             draw_text(
                 &weapon_text,
-                screen_width() / 2.0 - 60.0,
-                screen_height() / 2.0 + 265.0 + (i as f32 * 22.0),
-                16.0,
+                weapon_pos.x,
+                weapon_pos.y,
+                layout.s(16.0),
                 GRAY,
             );
         }
     }
 
     // Draw restart instructions
+    let prompt_x = layout.menu(260.0, 0.0).x;
     draw_text(
         "Press Return to Play Again",
-        screen_width() / 2.0 - 140.0,
-        screen_height() / 2.0 + 340.0,
-        22.0,
+        prompt_x,
+        layout.end_prompt_y(0),
+        layout.s(22.0),
         WHITE,
     );
 
+    let retry_text = format!("Press R to Retry (same seed {})", gs.seed);
+    draw_text(
+        &retry_text,
+        prompt_x,
+        layout.end_prompt_y(1),
+        layout.s(20.0),
+        LIGHTGRAY,
+    );
+
     // Handle restart
     if is_key_pressed(KeyCode::Enter) {
-        let assets = gs.assets.clone();
-        *gs = GameState::new(assets);
+        gs.restart();
+    } else if is_key_pressed(KeyCode::R) {
+        gs.retry_same_seed();
     }
 }
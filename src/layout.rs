@@ -0,0 +1,227 @@
+use macroquad::prelude::*;
+
+/// The HUD and menus are designed for a square window of this size
+pub const REFERENCE_SIZE: f32 = 800.0;
+/// Screens narrower or lower than this, like a minimized window reporting 0x0, are degenerate
+const MIN_SCREEN_SIZE: f32 = 16.0;
+/// Smallest scale factor, so sizes and font sizes never collapse to zero
+const MIN_SCALE: f32 = 0.05;
+
+/// Card row of the weapon selection, in reference coordinates
+const CARD_WIDTH: f32 = 170.0;
+const CARD_HEIGHT: f32 = 280.0;
+const CARD_SPACING: f32 = 20.0;
+const CARD_Y: f32 = 440.0;
+
+/// Prompt rows (restart, retry) at the bottom of the end screens, in reference coordinates
+const END_PROMPT_Y: f32 = 740.0;
+const END_PROMPT_SPACING: f32 = 26.0;
+
+/// Maps positions and sizes designed for the 800x800 reference window onto the current screen.
+/// HUD elements stick to the screen edges, menus are laid out in a centered reference square,
+/// everything is scaled by the same factor so nothing leaves the screen.
+#[derive(Debug, Clone, Copy)]
+pub struct Layout {
+    pub width: f32,
+    pub height: f32,
+    pub scale: f32,
+}
+
+impl Layout {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            width,
+            height,
+            scale: (width.min(height) / REFERENCE_SIZE).max(MIN_SCALE),
+        }
+    }
+
+    /// Whether the screen is too small to play on, e.g. while the window is minimized
+    pub fn is_degenerate(&self) -> bool {
+        !(self.width >= MIN_SCREEN_SIZE && self.height >= MIN_SCREEN_SIZE)
+    }
+
+    /// Layout for the current window size
+    pub fn current() -> Self {
+        Self::new(screen_width(), screen_height())
+    }
+
+    /// Scales a size, distance or font size given in reference pixels
+    pub fn s(&self, size: f32) -> f32 {
+        size * self.scale
+    }
+
+    /// Distance from the left screen edge
+    pub fn left(&self, offset: f32) -> f32 {
+        self.s(offset)
+    }
+
+    /// Distance from the right screen edge
+    pub fn right(&self, offset: f32) -> f32 {
+        self.width - self.s(offset)
+    }
+
+    /// Distance from the top screen edge
+    pub fn top(&self, offset: f32) -> f32 {
+        self.s(offset)
+    }
+
+    pub fn center_x(&self) -> f32 {
+        self.width / 2.0
+    }
+
+    pub fn center_y(&self) -> f32 {
+        self.height / 2.0
+    }
+
+    /// Position inside the centered reference square, for menus
+    pub fn menu(&self, x: f32, y: f32) -> Vec2 {
+        let origin = Vec2::new(
+            (self.width - self.s(REFERENCE_SIZE)) / 2.0,
+            (self.height - self.s(REFERENCE_SIZE)) / 2.0,
+        );
+        origin + Vec2::new(self.s(x), self.s(y))
+    }
+
+    /// X position that centers text of the given font size horizontally
+    pub fn centered_text_x(&self, text: &str, font_size: f32) -> f32 {
+        self.center_x() - measure_text(text, None, font_size as u16, 1.0).width / 2.0
+    }
+
+    /// Cards of the weapon selection, side by side and centered, narrower if they don't fit
+    pub fn card_rects(&self, num_cards: usize) -> Vec<Rect> {
+        let n = num_cards as f32;
+        let spacing = CARD_SPACING * (n - 1.0).max(0.0);
+        let card_width = CARD_WIDTH.min((REFERENCE_SIZE - 2.0 * CARD_SPACING - spacing) / n);
+        let total_width = card_width * n + spacing;
+        let start_x = (REFERENCE_SIZE - total_width) / 2.0;
+        (0..num_cards)
+            .map(|i| {
+                let pos = self.menu(start_x + (card_width + CARD_SPACING) * i as f32, CARD_Y);
+                Rect::new(pos.x, pos.y, self.s(card_width), self.s(CARD_HEIGHT))
+            })
+            .collect()
+    }
+
+    /// Text and icon scale on a card, below 1.0 when many cards made it narrower
+    pub fn card_text_scale(&self, card: Rect) -> f32 {
+        (card.w / self.s(CARD_WIDTH)).min(1.0)
+    }
+
+    /// Strip of a card between the key and the name where the weapon preview plays
+    pub fn card_preview(&self, card: Rect) -> Rect {
+        let margin = self.s(8.0);
+        Rect::new(
+            card.x + margin,
+            card.y + self.s(50.0),
+            card.w - 2.0 * margin,
+            self.s(70.0),
+        )
+    }
+
+    /// Baseline of the `row`-th prompt at the bottom of the game over and victory screens
+    pub fn end_prompt_y(&self, row: usize) -> f32 {
+        self.menu(0.0, END_PROMPT_Y + row as f32 * END_PROMPT_SPACING)
+            .y
+    }
+
+    /// Player health bar at the top center
+    pub fn health_bar(&self) -> Rect {
+        let width = self.s(200.0);
+        Rect::new(
+            self.center_x() - width / 2.0,
+            self.top(10.0),
+            width,
+            self.s(12.0),
+        )
+    }
+
+    /// Slot of the `index`-th weapon in the HUD weapon row at the top right
+    pub fn weapon_slot(&self, index: usize) -> Rect {
+        let slot_size = self.s(40.0);
+        let slot_spacing = self.s(8.0);
+        Rect::new(
+            self.right(200.0) + index as f32 * (slot_size + slot_spacing),
+            self.top(75.0),
+            slot_size,
+            slot_size,
+        )
+    }
+
+    #[cfg(test)]
+    pub fn contains(&self, rect: Rect) -> bool {
+        rect.x >= 0.0 && rect.y >= 0.0 && rect.right() <= self.width && rect.bottom() <= self.height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESOLUTIONS: [(f32, f32); 5] = [
+        (800.0, 800.0),
+        (1920.0, 1080.0),
+        (1080.0, 1920.0),
+        (640.0, 480.0),
+        (3840.0, 2160.0),
+    ];
+
+    #[test]
+    fn test_layout_stays_on_screen() {
+        for (width, height) in RESOLUTIONS {
+            let layout = Layout::new(width, height);
+
+            for card in layout.card_rects(5) {
+                assert!(
+                    layout.contains(card),
+                    "card {:?} at {}x{}",
+                    card,
+                    width,
+                    height
+                );
+            }
+            for card in layout.card_rects(7) {
+                let preview = layout.card_preview(card);
+                assert!(card.contains(preview.point()));
+                assert!(card.contains(preview.point() + preview.size()));
+            }
+            assert!(layout.contains(layout.health_bar()));
+            for i in 0..crate::weapon::MAX_WEAPON_SLOTS {
+                assert!(layout.contains(layout.weapon_slot(i)));
+            }
+
+            // Everything below the cards (subtitle and instructions) is still on screen
+            let below_cards = layout.menu(0.0, CARD_Y + CARD_HEIGHT + 60.0);
+            assert!(below_cards.y <= height);
+
+            // The restart and retry prompts of the end screens too
+            for row in 0..2 {
+                let prompt_y = layout.end_prompt_y(row);
+                assert!(
+                    prompt_y <= height,
+                    "end prompt {} at {}x{}",
+                    row,
+                    width,
+                    height
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_size_screen_is_degenerate() {
+        let layout = Layout::new(0.0, 0.0);
+        assert!(layout.is_degenerate());
+        assert!(layout.scale > 0.0);
+        assert!(layout.menu(20.0, 30.0).is_finite());
+        assert!(!Layout::new(640.0, 480.0).is_degenerate());
+    }
+
+    #[test]
+    fn test_reference_size_is_unscaled() {
+        let layout = Layout::new(REFERENCE_SIZE, REFERENCE_SIZE);
+        assert_eq!(layout.scale, 1.0);
+        assert_eq!(layout.menu(20.0, 30.0), Vec2::new(20.0, 30.0));
+        assert_eq!(layout.right(120.0), 680.0);
+    }
+}
@@ -1,34 +1,70 @@
 use macroquad::prelude::*;
 
+mod barrier;
 mod collision;
+mod console;
+mod damage_meter;
 mod enemy;
 mod entity;
 mod gamestate;
+mod layout;
+mod palette;
+mod pickup;
 mod player;
 mod projectile;
 mod roto_script;
+mod save;
+mod serde_compat;
+mod settings;
+mod targeting;
 mod visual_config;
 mod weapon;
 
 use gamestate::{GameState, GameStateEnum};
 
+use crate::roto_script::DEFAULT_SCRIPT_PATH;
 use crate::visual_config::Assets;
 
-pub const DT: f64 = 1.0 / 30.0;
+/// Logic updates per second unless the script sets another rate
+pub const DEFAULT_TICK_RATE: u32 = 30;
 
 fn window_conf() -> Conf {
     Conf {
         window_width: 800,
         window_height: 800,
         window_resizable: false,
-        fullscreen: false,
         window_title: "Macro Roto - The Auto Battler".to_owned(),
         ..Default::default()
     }
 }
 
+/// Reads the script file from `--script path.roto`, defaults to the main script
+fn script_path_from_args() -> String {
+    let args: Vec<String> = std::env::args().collect();
+    match args.iter().position(|arg| arg == "--script") {
+        // Make it absolute, the working directory may change on macOS
+        Some(i) => match args.get(i + 1) {
+            Some(path) => std::path::absolute(path)
+                .map(|path| path.to_string_lossy().into_owned())
+                .unwrap_or_else(|_| path.clone()),
+            None => {
+                eprintln!("--script expects a path, using {}", DEFAULT_SCRIPT_PATH);
+                DEFAULT_SCRIPT_PATH.to_owned()
+            }
+        },
+        None => DEFAULT_SCRIPT_PATH.to_owned(),
+    }
+}
+
+/// Sandbox mode with `--sandbox`, no waves but practice dummies for tuning weapons
+fn sandbox_from_args() -> bool {
+    std::env::args().any(|arg| arg == "--sandbox")
+}
+
 #[macroquad::main(window_conf)]
 async fn main() {
+    let script_path = script_path_from_args();
+
     #[cfg(target_os = "macos")]
     {
         use std::env;
@@ -40,9 +76,19 @@ async fn main() {
         }
     }
 
-    let mut gs = GameState::new(Assets {
-        char_tex: Some(load_texture("assets/elf_char.png").await.unwrap()),
-    });
+    let mut gs = GameState::new(
+        Assets {
+            char_tex: Some(load_texture("assets/elf_char.png").await.unwrap()),
+        },
+        script_path,
+    );
+    gs.sandbox = sandbox_from_args();
+    // The settings are read after the working directory moved to the bundle resources,
+    // the window config is built before that
+    if gs.settings.fullscreen {
+        set_fullscreen(true);
+    }
+    gs.roto_manager.print_function_report();
 
     loop {
         match gs.state {
@@ -62,7 +108,6 @@ async fn main() {
             GameStateEnum::Playing => {
                 gs.process_global_input();
                 gamestate::playing::process(&mut gs);
-                clear_background(BLACK);
                 gamestate::playing::draw(&gs);
             }
         }
@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use crate::visual_config::{
+    ColorConfig, EnemyVisualConfig, GameVisualConfig, ProjectileVisualConfig,
+};
+
+/// Optional palette file next to the game, see `palette.example.json`
+pub const PALETTE_PATH: &str = "palette.json";
+
+/// Named colors that override the visual config from the Roto script, so the game can be
+/// rethemed without touching any code.
+///
+/// The file is a JSON object mapping color names to `[r, g, b]` or `[r, g, b, a]` with
+/// components from 0.0 to 1.0. Names the palette doesn't contain keep the script colors.
+///
+/// Known names:
+/// - `player`, `player_indicator`
+/// - `basic_enemy`, `chaser_enemy`, `teleporter_enemy`, `berserker_enemy`, `phaser_enemy`,
+///   `builder_enemy`, `glass_enemy`, each also with an `_indicator` suffix
+/// - `energy_ball`, `pulse`, `homing_missile`, `gravity_well`, each also with `_secondary`
+///   and `_indicator`
+/// - `pulse_blend_inner`, `pulse_blend_outer`
+/// - `background_start`, `background_end`
+#[derive(Debug, Default)]
+pub struct Palette {
+    colors: HashMap<String, ColorConfig>,
+}
+
+impl Palette {
+    /// Loads the palette, a missing file gives an empty palette, an invalid one is reported
+    pub fn load(path: &str) -> Self {
+        let Ok(json) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        match Self::from_json(&json) {
+            Ok(palette) => {
+                println!("✓ Loaded {} colors from {}", palette.colors.len(), path);
+                palette
+            }
+            Err(err) => {
+                eprintln!("ERROR in {}: {}", path, err);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let raw: HashMap<String, Vec<f32>> =
+            serde_json::from_str(json).map_err(|err| err.to_string())?;
+
+        let mut colors = HashMap::new();
+        for (name, components) in raw {
+            let color = match components[..] {
+                [r, g, b] => ColorConfig::new(r, g, b, 1.0),
+                [r, g, b, a] => ColorConfig::new(r, g, b, a),
+                _ => return Err(format!("'{}' needs 3 or 4 color components", name)),
+            };
+            colors.insert(name, color);
+        }
+        Ok(Self { colors })
+    }
+
+    pub fn get(&self, name: &str) -> Option<ColorConfig> {
+        self.colors.get(name).copied()
+    }
+
+    /// Replaces the colors of `config` that the palette has a name for
+    pub fn apply(&self, config: &mut GameVisualConfig) {
+        let set = |color: &mut ColorConfig, name: &str| {
+            if let Some(palette_color) = self.get(name) {
+                *color = palette_color;
+            }
+        };
+        let set_enemy = |enemy: &mut EnemyVisualConfig, name: &str| {
+            set(&mut enemy.circle_color, name);
+            set(&mut enemy.indicator_color, &format!("{}_indicator", name));
+        };
+        let set_projectile = |projectile: &mut ProjectileVisualConfig, name: &str| {
+            set(&mut projectile.primary_color, name);
+            set(
+                &mut projectile.secondary_color,
+                &format!("{}_secondary", name),
+            );
+            set(
+                &mut projectile.indicator_color,
+                &format!("{}_indicator", name),
+            );
+        };
+
+        set(&mut config.player.circle_color, "player");
+        set(&mut config.player.indicator_color, "player_indicator");
+        set_enemy(&mut config.basic_enemy, "basic_enemy");
+        set_enemy(&mut config.chaser_enemy, "chaser_enemy");
+        set_enemy(&mut config.teleporter_enemy, "teleporter_enemy");
+        set_enemy(&mut config.berserker_enemy, "berserker_enemy");
+        set_enemy(&mut config.phaser_enemy, "phaser_enemy");
+        set_enemy(&mut config.builder_enemy, "builder_enemy");
+        set_enemy(&mut config.glass_enemy, "glass_enemy");
+        set_projectile(&mut config.energy_ball, "energy_ball");
+        set_projectile(&mut config.pulse, "pulse");
+        set_projectile(&mut config.homing_missile, "homing_missile");
+        set_projectile(&mut config.gravity_well, "gravity_well");
+        set(&mut config.pulse_blend.inner_color, "pulse_blend_inner");
+        set(&mut config.pulse_blend.outer_color, "pulse_blend_outer");
+        set(&mut config.background.inner_color, "background_start");
+        set(&mut config.background.outer_color, "background_end");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_overrides_named_colors_only() {
+        let palette = Palette::from_json(
+            r#"{ "player": [0.1, 0.2, 0.3], "chaser_enemy_indicator": [1.0, 0.0, 0.0, 0.5] }"#,
+        )
+        .unwrap();
+
+        let mut config = GameVisualConfig::default();
+        let basic_before = config.basic_enemy.circle_color;
+        palette.apply(&mut config);
+
+        let player = config.player.circle_color;
+        assert_eq!(
+            (player.r, player.g, player.b, player.a),
+            (0.1, 0.2, 0.3, 1.0)
+        );
+        assert_eq!(config.chaser_enemy.indicator_color.a, 0.5);
+        // Colors without a palette entry keep the configured value
+        assert_eq!(config.basic_enemy.circle_color.r, basic_before.r);
+    }
+
+    #[test]
+    fn test_invalid_palette_is_an_error() {
+        assert!(Palette::from_json("not json").is_err());
+        assert!(Palette::from_json(r#"{ "player": [1.0, 0.0] }"#).is_err());
+        assert!(Palette::load("does/not/exist.json").get("player").is_none());
+    }
+}
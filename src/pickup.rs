@@ -0,0 +1,69 @@
+use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::collision::{Collidable, Collider};
+
+/// Seconds a pickup lies around before it vanishes
+const PICKUP_TIME: f32 = 10.0;
+/// Pickups blink during their last seconds
+const PICKUP_BLINK_TIME: f32 = 3.0;
+const PICKUP_RADIUS: f32 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PickupType {
+    Shield, // Absorbs the next enemy contact
+}
+
+/// Item dropped by a killed enemy, collected by touching it
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Pickup {
+    pub pickup_type: PickupType,
+    #[serde(with = "crate::serde_compat::vec2")]
+    pub pos: Vec2,
+    pub time_remaining: f32,
+}
+
+impl Pickup {
+    pub fn new(pickup_type: PickupType, pos: Vec2) -> Self {
+        Self {
+            pickup_type,
+            pos,
+            time_remaining: PICKUP_TIME,
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.time_remaining -= dt;
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.time_remaining <= 0.0
+    }
+
+    pub fn draw(&self, reduced_motion: bool) {
+        let mut color = match self.pickup_type {
+            PickupType::Shield => SKYBLUE,
+        };
+        if self.time_remaining < PICKUP_BLINK_TIME {
+            if reduced_motion {
+                color.a *= 0.5;
+            } else if (self.time_remaining * 4.0).fract() < 0.5 {
+                color.a *= 0.3;
+            }
+        }
+        draw_circle_lines(self.pos.x, self.pos.y, PICKUP_RADIUS, 2.0, color);
+        draw_circle(self.pos.x, self.pos.y, PICKUP_RADIUS * 0.5, color);
+    }
+}
+
+impl Collidable for Pickup {
+    fn collider(&self) -> Collider {
+        Collider::Circle {
+            radius: PICKUP_RADIUS,
+        }
+    }
+
+    fn position(&self) -> Vec2 {
+        self.pos
+    }
+}
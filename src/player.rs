@@ -1,27 +1,136 @@
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::collision::{Collidable, Collider};
-use crate::entity::{EntityStats, SpawnCommand};
+use crate::enemy::Enemy;
+use crate::entity::{EntityId, EntityStats, SpawnCommand, apply_motion, reference_ticks};
+use crate::roto_script::ContactModel;
+use crate::targeting::count_within;
 use crate::visual_config::{PlayerVisualConfig, draw_direction_indicator};
-use crate::weapon::{Weapon, WeaponType};
+use crate::weapon::{Weapon, WeaponTuning, WeaponType};
 
-#[derive(Debug, Clone)]
+/// Seconds the player can't be damaged again after a hit
+const INVULNERABLE_TIME: f32 = 1.0;
+/// Seconds after a knockback in which the screen edges hold the player back
+const KNOCKBACK_TIME: f32 = 0.5;
+
+/// Reward picked during the break after a wave, the amounts come from `WaveRewards`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WaveReward {
+    Heal,         // Restores some health
+    FireRateBuff, // Weapons recharge faster for a while
+    BonusXp,      // Extra XP, may level up right away
+}
+
+/// Amounts of the rewards offered between waves
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WaveRewards {
+    pub heal: f32,            // Health restored by the heal reward
+    pub buff_multiplier: f32, // Weapon recharge speed while the buff lasts
+    pub buff_duration: f32,   // Seconds the buff lasts
+    pub bonus_xp: u32,        // XP of the bonus XP reward
+}
+
+impl Default for WaveRewards {
+    fn default() -> Self {
+        Self {
+            heal: 20.0,
+            buff_multiplier: 1.5,
+            buff_duration: 15.0,
+            bonus_xp: 10,
+        }
+    }
+}
+
+/// Optional pressure on a player who stays in one place, enemies spawn around them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AntiCampConfig {
+    pub speed_threshold: f32, // Below this speed the player counts as standing still
+    pub time_limit: f32,      // Seconds of standing still before enemies spawn, 0 disables it
+    pub enemy_count: u32,     // Enemies spawned each time the limit is reached
+    pub distance: f32,        // Distance from the player the enemies spawn at
+}
+
+impl Default for AntiCampConfig {
+    fn default() -> Self {
+        Self {
+            speed_threshold: 0.5,
+            time_limit: 0.0,
+            enemy_count: 3,
+            distance: 200.0,
+        }
+    }
+}
+
+impl AntiCampConfig {
+    pub fn enabled(&self) -> bool {
+        self.time_limit > 0.0 && self.enemy_count > 0
+    }
+
+    /// Advances `stillness_timer` for a player moving at `speed`, movement resets it.
+    /// Returns true when the player stood still for too long, the timer then starts over.
+    pub fn update(&self, stillness_timer: &mut f32, speed: f32, dt: f32) -> bool {
+        if !self.enabled() || speed >= self.speed_threshold {
+            *stillness_timer = 0.0;
+            return false;
+        }
+        *stillness_timer += dt;
+        if *stillness_timer >= self.time_limit {
+            *stillness_timer = 0.0;
+            return true;
+        }
+        false
+    }
+
+    /// Spawn positions evenly around `pos`, kept inside the screen of size `bounds`
+    pub fn spawn_positions(&self, pos: Vec2, bounds: Vec2) -> Vec<Vec2> {
+        let offset = rand::gen_range(0.0, std::f32::consts::TAU);
+        (0..self.enemy_count)
+            .map(|i| {
+                let angle = offset + i as f32 * std::f32::consts::TAU / self.enemy_count as f32;
+                let spawn = pos + Vec2::from_angle(angle) * self.distance;
+                spawn.clamp(Vec2::ZERO, bounds)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
+    pub id: EntityId,
+    #[serde(with = "crate::serde_compat::vec2")]
     pub pos: Vec2,
+    #[serde(with = "crate::serde_compat::vec2")]
+    pub prev_pos: Vec2, // Position at the start of the logic step, for render interpolation
+    #[serde(with = "crate::serde_compat::vec2")]
     pub vel: Vec2,
+    #[serde(with = "crate::serde_compat::vec2")]
     pub facing: Vec2, // Direction player is facing for weapon firing
     stats: EntityStats,
     weapons: Vec<Weapon>,
     visual_config: PlayerVisualConfig,
     pub xp: u32,
     pub level: u32,
+    charging: bool, // Charge key is held down
+    pub health: f32,
+    pub invulnerable_timer: f32, // Counts down from INVULNERABLE_TIME after each hit
+    #[serde(default)]
+    pub knockback_timer: f32, // Counts down from KNOCKBACK_TIME after each knockback
+    #[serde(default)]
+    pub shield_charges: u32, // Enemy contacts absorbed without damage
+    #[serde(default)]
+    pub buff_timer: f32, // Seconds left on the fire rate buff
+    #[serde(default)]
+    pub buff_multiplier: f32, // Weapon recharge speed while the buff lasts
 }
 
 impl Player {
-    pub fn new(x: f32, y: f32, stats: EntityStats) -> Self {
+    pub fn new(id: EntityId, x: f32, y: f32, stats: EntityStats) -> Self {
         // Player starts without a weapon - it will be set by weapon selection popup
         Self {
+            id,
             pos: Vec2::new(x, y),
+            prev_pos: Vec2::new(x, y),
             vel: Vec2::ZERO,
             facing: Vec2::new(1.0, 0.0), // Start facing right
             stats,
@@ -29,16 +138,114 @@ impl Player {
             visual_config: PlayerVisualConfig::default(),
             xp: 0,
             level: 0,
+            charging: false,
+            health: stats.max_health,
+            invulnerable_timer: 0.0,
+            knockback_timer: 0.0,
+            shield_charges: 0,
+            buff_timer: 0.0,
+            buff_multiplier: 1.0,
         }
     }
 
+    // Resets the run-specific state, the player keeps its EntityId
     pub fn reset(&mut self, x: f32, y: f32) {
         self.pos = Vec2::new(x, y);
+        self.prev_pos = self.pos;
         self.vel = Vec2::ZERO;
         self.facing = Vec2::new(1.0, 0.0);
         self.weapons.clear();
         self.xp = 0;
         self.level = 0;
+        self.charging = false;
+        self.health = self.stats.max_health;
+        self.invulnerable_timer = 0.0;
+        self.knockback_timer = 0.0;
+        self.shield_charges = 0;
+        self.buff_timer = 0.0;
+    }
+
+    /// Spends a shield charge on a hit instead of health, the i-frames start as after damage
+    pub fn absorb_with_shield(&mut self) -> bool {
+        if self.is_invulnerable() || self.shield_charges == 0 {
+            return false;
+        }
+        self.shield_charges -= 1;
+        self.invulnerable_timer = INVULNERABLE_TIME;
+        true
+    }
+
+    /// Applies damage unless the player is still invulnerable from the last hit
+    pub fn take_damage(&mut self, damage: f32) -> bool {
+        if self.is_invulnerable() {
+            return false;
+        }
+        self.health -= damage;
+        self.invulnerable_timer = INVULNERABLE_TIME;
+        true
+    }
+
+    /// Damage from touching an enemy. With the per-enemy model the i-frames don't protect,
+    /// the caller checks the cooldown of the enemy instead.
+    pub fn take_contact_damage(&mut self, damage: f32, model: ContactModel) -> bool {
+        match model {
+            ContactModel::Global => self.take_damage(damage),
+            ContactModel::PerEnemy => {
+                self.health -= damage;
+                true
+            }
+        }
+    }
+
+    /// Pushes the player along `normal`, which points away from whatever hit the player
+    pub fn apply_knockback(&mut self, normal: Vec2, strength: f32) {
+        self.vel += normal.normalize_or_zero() * strength;
+        self.knockback_timer = KNOCKBACK_TIME;
+    }
+
+    /// Heals up to max health, returns the amount actually healed
+    pub fn heal(&mut self, amount: f32) -> f32 {
+        let healed = amount.min(self.stats.max_health - self.health).max(0.0);
+        self.health += healed;
+        healed
+    }
+
+    /// Applies a reward from the wave break, returns the number of level ups it caused
+    pub fn claim_wave_reward(&mut self, reward: WaveReward, rewards: &WaveRewards) -> u32 {
+        match reward {
+            WaveReward::Heal => {
+                self.heal(rewards.heal);
+                0
+            }
+            WaveReward::FireRateBuff => {
+                self.buff_timer = rewards.buff_duration;
+                self.buff_multiplier = rewards.buff_multiplier;
+                0
+            }
+            WaveReward::BonusXp => self.add_xp(rewards.bonus_xp),
+        }
+    }
+
+    pub fn is_buffed(&self) -> bool {
+        self.buff_timer > 0.0
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_timer > 0.0
+    }
+
+    /// Right after a hit the screen edges stop the player instead of ending the run, also in
+    /// the per-enemy contact model which has no i-frames
+    pub fn is_held_in_bounds(&self) -> bool {
+        self.is_invulnerable() || self.knockback_timer > 0.0
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0.0
+    }
+
+    pub fn get_max_health(&self) -> f32 {
+        self.stats.max_health
     }
 
     pub fn xp_for_level(level: u32) -> u32 {
@@ -75,18 +282,42 @@ impl Player {
         self.level
     }
 
+    pub fn get_stats(&self) -> EntityStats {
+        self.stats
+    }
+
+    pub fn get_radius(&self) -> f32 {
+        self.stats.radius
+    }
+
     pub fn get_xp(&self) -> u32 {
         self.xp
     }
 
-    pub fn add_weapon(&mut self, weapon_type: WeaponType) {
-        let weapon = Weapon::new(weapon_type);
+    /// Adds a new weapon with `tuning` applied, the weapons already held keep their upgrades
+    pub fn add_weapon(&mut self, weapon_type: WeaponType, tuning: &WeaponTuning) {
+        let mut weapon = Weapon::new(weapon_type);
+        weapon.apply_tuning(tuning);
         self.weapons.push(weapon);
     }
 
-    pub fn level_up_weapon(&mut self, index: usize) {
+    /// Swaps the weapon at `index` for a new one, returns the type of the replaced weapon
+    pub fn replace_weapon(
+        &mut self,
+        index: usize,
+        weapon_type: WeaponType,
+        tuning: &WeaponTuning,
+    ) -> Option<WeaponType> {
+        let weapon = self.weapons.get_mut(index)?;
+        let replaced = weapon.weapon_type;
+        *weapon = Weapon::new(weapon_type);
+        weapon.apply_tuning(tuning);
+        Some(replaced)
+    }
+
+    pub fn level_up_weapon(&mut self, index: usize, tuning: &WeaponTuning) {
         if index < self.weapons.len() {
-            self.weapons[index].level_up();
+            self.weapons[index].level_up(tuning);
         }
     }
 
@@ -96,6 +327,14 @@ impl Player {
 
     pub fn override_stats(&mut self, stats: EntityStats) {
         self.stats = stats;
+        self.health = self.health.min(stats.max_health);
+    }
+
+    /// Retunes every weapon after a script reload, their level ups and mastery tiers stay
+    pub fn override_weapon_tuning(&mut self, tuning: WeaponTuning) {
+        for weapon in self.weapons.iter_mut() {
+            weapon.retune(&tuning);
+        }
     }
 
     pub fn override_visual_config(&mut self, visual_config: PlayerVisualConfig) {
@@ -106,27 +345,56 @@ impl Player {
         &self.weapons
     }
 
-    pub fn draw(&self) {
-        draw_circle(
-            self.pos.x,
-            self.pos.y,
-            self.stats.radius,
-            self.visual_config.circle_color.to_color(),
-        );
+    pub fn draw(&self, alpha: f32, reduced_motion: bool) {
+        let pos = self.prev_pos.lerp(self.pos, alpha);
+        let mut circle_color = self.visual_config.circle_color.to_color();
+        if self.is_invulnerable() {
+            if reduced_motion {
+                // Steadily faded instead of flickering
+                circle_color.a *= 0.5;
+            } else if (get_time() * 5.0).fract() < 0.5 {
+                // Flicker while invulnerable
+                circle_color.a *= 0.3;
+            }
+        }
+        draw_circle(pos.x, pos.y, self.stats.radius, circle_color);
+
+        // One shield ring per charge
+        for i in 0..self.shield_charges {
+            let radius = self.stats.radius + 5.0 + i as f32 * 4.0;
+            draw_circle_lines(pos.x, pos.y, radius, 2.0, SKYBLUE);
+        }
 
         // Draw direction indicator triangle
         let mouse_pos = mouse_position();
-        let to_mouse = Vec2::new(mouse_pos.0, mouse_pos.1) - self.pos;
+        let to_mouse = Vec2::new(mouse_pos.0, mouse_pos.1) - pos;
         draw_direction_indicator(
-            self.pos,
+            pos,
             to_mouse,
             self.stats.radius,
             self.visual_config.indicator_color,
             self.visual_config.indicator_size,
         );
+
+        // Draw charge bar below the player while a charged weapon is charging
+        if let Some(charge) = self
+            .weapons
+            .iter()
+            .map(|w| w.charge_fraction())
+            .find(|c| *c > 0.0)
+        {
+            let width = self.stats.radius * 2.0;
+            let x = pos.x - self.stats.radius;
+            let y = pos.y + self.stats.radius + 6.0;
+            draw_rectangle(x, y, width, 4.0, DARKGRAY);
+            let color = if charge >= 1.0 { RED } else { ORANGE };
+            draw_rectangle(x, y, width * charge, 4.0, color);
+        }
     }
 
-    pub fn input(&mut self) {
+    /// Reads the keys and the mouse, `dt` scales the acceleration to the tick rate. While
+    /// `typing` in the console, space types instead of charging.
+    pub fn input(&mut self, dt: f32, typing: bool) {
         let mut acceleration = Vec2::ZERO;
 
         if is_key_down(KeyCode::Left) {
@@ -142,7 +410,10 @@ impl Player {
             acceleration.y += self.stats.acceleration;
         }
 
-        self.vel += acceleration;
+        self.vel += acceleration * reference_ticks(dt);
+
+        // Hold space to charge manually fired weapons
+        self.charging = !typing && is_key_down(KeyCode::Space);
 
         // Update facing direction based on mouse cursor position
         let mouse_pos = mouse_position();
@@ -155,22 +426,45 @@ impl Player {
         self.clamp_velocity();
     }
 
-    pub fn update(&mut self, dt: f32) -> Vec<SpawnCommand> {
-        self.pos += self.vel;
+    /// Moves the player and fires the weapons, `spawn_commands` is cleared and refilled so
+    /// the caller can reuse the same buffer every tick. `enemies` lets weapons react to how
+    /// crowded it is around the player.
+    pub fn update(&mut self, dt: f32, enemies: &[Enemy], spawn_commands: &mut Vec<SpawnCommand>) {
+        if self.invulnerable_timer > 0.0 {
+            self.invulnerable_timer -= dt;
+        }
+        if self.knockback_timer > 0.0 {
+            self.knockback_timer -= dt;
+        }
 
-        // Apply friction
-        self.vel *= self.stats.friction;
+        // Move and apply friction, the same distance at any tick rate
+        apply_motion(&mut self.pos, &mut self.vel, self.stats.friction, dt);
 
-        // Update weapons and collect spawn commands
-        let mut spawn_commands = Vec::new();
+        // Update weapons and collect spawn commands, the buff makes them recharge faster
+        spawn_commands.clear();
+        let weapon_dt = if self.is_buffed() {
+            self.buff_timer -= dt;
+            dt * self.buff_multiplier
+        } else {
+            dt
+        };
 
         for weapon in &mut self.weapons {
-            weapon.update(dt);
-            let commands = weapon.fire(self.pos, self.facing);
-            spawn_commands.extend(commands);
+            weapon.update(weapon_dt);
+            if weapon.weapon_type == WeaponType::Cannon {
+                // The cannon doesn't auto-fire, it is charged and released by the player
+                weapon.charge(dt, self.charging, self.pos, self.facing, spawn_commands);
+            } else if weapon.weapon_type == WeaponType::Tether {
+                // The tether doesn't fire, see GameState::update_tethers
+            } else {
+                let nearby = if weapon.weapon_type == WeaponType::Adaptive {
+                    count_within(self.pos, weapon.stats.adaptive_radius, enemies)
+                } else {
+                    0
+                };
+                weapon.fire(self.pos, self.facing, nearby, spawn_commands);
+            }
         }
-
-        spawn_commands
     }
 
     fn clamp_velocity(&mut self) {
@@ -192,3 +486,229 @@ impl Collidable for Player {
         self.pos
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_player() -> Player {
+        let stats = EntityStats {
+            radius: 20.0,
+            max_speed: 5.0,
+            acceleration: 1.0,
+            friction: 0.9,
+            max_health: 10.0,
+        };
+        Player::new(0, 0.0, 0.0, stats)
+    }
+
+    #[test]
+    fn test_reused_spawn_buffer_gives_same_commands() {
+        let mut fresh = test_player();
+        let mut reused = test_player();
+        for player in [&mut fresh, &mut reused] {
+            player.add_weapon(WeaponType::EnergyBall, &WeaponTuning::default());
+            player.add_weapon(WeaponType::Pulse, &WeaponTuning::default());
+            player.level_up_weapon(0, &WeaponTuning::default());
+        }
+
+        // A buffer left over from the last tick, with a stale command in it
+        let mut buffer = vec![SpawnCommand::Enemy {
+            enemy_type: crate::enemy::EnemyType::Basic,
+            pos: Vec2::ZERO,
+        }];
+        for _ in 0..60 {
+            let mut commands = Vec::new();
+            fresh.update(1.0 / 30.0, &[], &mut commands);
+            reused.update(1.0 / 30.0, &[], &mut buffer);
+            assert_eq!(format!("{:?}", commands), format!("{:?}", buffer));
+        }
+    }
+
+    #[test]
+    fn test_motion_is_the_same_at_any_tick_rate() {
+        let mut distances = Vec::new();
+        for tick_rate in [30, 60, 144] {
+            let mut player = test_player();
+            player.vel = Vec2::new(5.0, -2.0);
+            let dt = 1.0 / tick_rate as f32;
+            let mut commands = Vec::new();
+            for _ in 0..tick_rate {
+                player.update(dt, &[], &mut commands);
+            }
+            distances.push((player.pos, player.vel));
+        }
+        for (pos, vel) in &distances[1..] {
+            assert!(
+                pos.distance(distances[0].0) < 1e-2,
+                "{} vs {}",
+                pos,
+                distances[0].0
+            );
+            assert!(vel.distance(distances[0].1) < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_shield_absorbs_a_hit() {
+        let mut player = test_player();
+        player.shield_charges = 1;
+
+        // The shielded hit costs the charge but no health
+        assert!(player.absorb_with_shield());
+        assert_eq!(player.shield_charges, 0);
+        assert_eq!(player.health, player.get_max_health());
+
+        // With the shield gone the next hit after the i-frames hurts
+        player.invulnerable_timer = 0.0;
+        assert!(!player.absorb_with_shield());
+        assert!(player.take_damage(3.0));
+        assert_eq!(player.health, player.get_max_health() - 3.0);
+    }
+
+    #[test]
+    fn test_knockback_pushes_along_contact_normal() {
+        let mut player = test_player();
+        player.vel = Vec2::new(2.0, 0.0);
+
+        // Hit by an enemy coming from the right, the normal points to the left
+        let normal = Vec2::new(-1.0, 0.0);
+        let vel_before = player.vel;
+        player.apply_knockback(normal, 5.0);
+
+        let delta = player.vel - vel_before;
+        assert!((delta.normalize() - normal).length() < 1e-5);
+        assert!((delta.length() - 5.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_lifesteal_heals_fraction_of_damage() {
+        let mut player = test_player();
+        player.health = 2.0;
+
+        let mut weapon = Weapon::new(WeaponType::EnergyBall);
+        weapon.stats.lifesteal = 0.5;
+
+        let healed = player.heal(weapon.lifesteal_heal(10.0));
+        assert_eq!(healed, 5.0);
+        assert_eq!(player.health, 7.0);
+
+        // Healing is capped at max health
+        let healed = player.heal(weapon.lifesteal_heal(10.0));
+        assert_eq!(healed, 3.0);
+        assert_eq!(player.health, 10.0);
+    }
+
+    #[test]
+    fn test_heal_reward_restores_configured_health() {
+        let mut player = test_player();
+        player.health = 2.0;
+        let rewards = WaveRewards {
+            heal: 5.0,
+            ..WaveRewards::default()
+        };
+
+        let level_ups = player.claim_wave_reward(WaveReward::Heal, &rewards);
+        assert_eq!(level_ups, 0);
+        assert_eq!(player.health, 7.0);
+
+        // Never above max health
+        player.claim_wave_reward(WaveReward::Heal, &rewards);
+        assert_eq!(player.health, player.get_max_health());
+    }
+
+    #[test]
+    fn test_standing_still_triggers_pressure_and_moving_resets() {
+        let anti_camp = AntiCampConfig {
+            time_limit: 1.0,
+            ..AntiCampConfig::default()
+        };
+        let dt = 0.25;
+        let mut timer = 0.0;
+
+        // Moving around never triggers it
+        for _ in 0..10 {
+            assert!(!anti_camp.update(&mut timer, 3.0, dt));
+        }
+        assert_eq!(timer, 0.0);
+
+        // Standing still for a while, then a step resets the timer
+        anti_camp.update(&mut timer, 0.0, dt);
+        anti_camp.update(&mut timer, 0.0, dt);
+        assert!(timer > 0.0);
+        anti_camp.update(&mut timer, 3.0, dt);
+        assert_eq!(timer, 0.0);
+
+        // A full second without moving spawns the pressure
+        let triggered = (0..4).any(|_| anti_camp.update(&mut timer, 0.1, dt));
+        assert!(triggered);
+        let bounds = Vec2::new(800.0, 600.0);
+        let positions = anti_camp.spawn_positions(Vec2::new(10.0, 10.0), bounds);
+        assert_eq!(positions.len(), anti_camp.enemy_count as usize);
+        assert!(
+            positions
+                .iter()
+                .all(|p| p.x >= 0.0 && p.y >= 0.0 && p.x <= bounds.x && p.y <= bounds.y)
+        );
+
+        // Disabled by default
+        assert!(!AntiCampConfig::default().update(&mut timer, 0.0, 10.0));
+    }
+
+    #[test]
+    fn test_per_enemy_contacts_hit_in_the_same_window() {
+        let mut first = Enemy::for_test(1, Vec2::ZERO);
+        let mut second = Enemy::for_test(2, Vec2::ZERO);
+        let cooldown = 1.0;
+
+        // Both enemies touch the player in the same tick and both hurt
+        let mut player = test_player();
+        for enemy in [&mut first, &mut second] {
+            assert!(enemy.try_contact_hit(cooldown));
+            assert!(player.take_contact_damage(2.0, ContactModel::PerEnemy));
+        }
+        assert_eq!(player.health, 6.0);
+
+        // The same enemy has to wait for its own cooldown
+        assert!(!first.try_contact_hit(cooldown));
+        first.update(cooldown, None);
+        assert!(first.try_contact_hit(cooldown));
+
+        // With the global model the i-frames of the first hit block the second
+        let mut player = test_player();
+        assert!(player.take_contact_damage(2.0, ContactModel::Global));
+        assert!(!player.take_contact_damage(2.0, ContactModel::Global));
+        assert_eq!(player.health, 8.0);
+    }
+
+    #[test]
+    fn test_new_weapons_and_reloads_keep_tether_upgrades() {
+        let tuning = WeaponTuning::default();
+        let mut player = test_player();
+        player.add_weapon(WeaponType::Tether, &tuning);
+        for _ in 0..5 {
+            player.level_up_weapon(0, &tuning);
+        }
+        for _ in 0..tuning.mastery.kills_per_tier {
+            player.get_weapons_mut()[0].add_kill();
+        }
+        let upgraded = player.get_weapons()[0].stats.tether;
+        assert!(upgraded.dps > tuning.tether.dps);
+
+        player.add_weapon(WeaponType::EnergyBall, &tuning);
+        assert_eq!(player.get_weapons()[0].stats.tether, upgraded);
+
+        // A script reload retunes the base stats, the upgrades are applied on top again
+        player.override_weapon_tuning(tuning);
+        assert_eq!(player.get_weapons()[0].stats.tether, upgraded);
+    }
+
+    #[test]
+    fn test_players_from_old_saves_load() {
+        // Saves from before the shield pickups lack the charges
+        let mut json = serde_json::to_value(test_player()).unwrap();
+        json.as_object_mut().unwrap().remove("shield_charges");
+        let player: Player = serde_json::from_value(json).unwrap();
+        assert_eq!(player.shield_charges, 0);
+    }
+}
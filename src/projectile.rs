@@ -1,73 +1,164 @@
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 use crate::collision::{Collidable, Collider};
-use crate::entity::EntityId;
-use crate::visual_config::{ProjectileVisualConfig, draw_direction_indicator};
+use crate::entity::{EntityId, PLAYER_ID};
+use crate::targeting::nearest_enemy;
+use crate::visual_config::{BlendConfig, ProjectileVisualConfig, draw_direction_indicator};
+use crate::weapon::{ReplacedProjectiles, SlowConfig, WeaponType};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum ProjectileType {
     EnergyBall,
     Pulse,
     HomingMissile,
+    GravityWell,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ProjectileStats {
     pub damage: f32,
     pub speed: f32,
-    pub radius: f32, // For EnergyBall and HomingMissile (circle)
+    pub radius: f32, // For EnergyBall and HomingMissile (circle), reach of a GravityWell
     pub width: f32,  // For Pulse (AABB)
     pub height: f32, // For Pulse (AABB)
     pub time_to_live: f32,
     pub turning_rate: f32, // For HomingMissile steering speed (radians per second)
+    pub pulse_expands: bool, // For Pulse, grow from small to full size over the lifetime
+    pub slow: SlowConfig,  // Slow debuff applied to enemies on contact
+    #[serde(default)]
+    pub falloff: f32, // For Pulse, fraction of the damage lost at the edge, 0.0 means full damage everywhere
+    #[serde(default)]
+    pub pull_strength: f32, // For GravityWell, speed per second added towards the center
 }
 
+/// Starting size of an expanding pulse relative to its full size
+const MIN_PULSE_SCALE: f32 = 0.2;
+/// Radians per second the swirl of a gravity well turns
+const GRAVITY_WELL_SPIN: f32 = 4.0;
+
+impl ProjectileStats {
+    pub fn energy_ball_default() -> Self {
+        Self {
+            damage: 10.0,
+            speed: 300.0,
+            radius: 8.0,
+            width: 0.0,  // Not used for energy ball
+            height: 0.0, // Not used for energy ball
+            time_to_live: 2.0,
+            turning_rate: 0.0, // Not used for energy ball
+            pulse_expands: false,
+            slow: SlowConfig::default(),
+            falloff: 0.0,
+            pull_strength: 0.0,
+        }
+    }
+
+    pub fn pulse_default() -> Self {
+        Self {
+            damage: 15.0,
+            speed: 0.0,  // Not used for pulse
+            radius: 0.0, // Not used for pulse
+            width: 100.0,
+            height: 100.0,
+            time_to_live: 0.3,
+            turning_rate: 0.0, // Not used for pulse
+            pulse_expands: false,
+            slow: SlowConfig::default(),
+            falloff: 0.0,
+            pull_strength: 0.0,
+        }
+    }
+
+    pub fn homing_missile_default() -> Self {
+        Self {
+            damage: 20.0,
+            speed: 250.0,
+            radius: 6.0,
+            width: 0.0,  // Not used for homing missile
+            height: 0.0, // Not used for homing missile
+            time_to_live: 3.0,
+            turning_rate: 3.0, // 3 radians per second turning rate
+            pulse_expands: false,
+            slow: SlowConfig::default(),
+            falloff: 0.0,
+            pull_strength: 0.0,
+        }
+    }
+
+    pub fn gravity_well_default() -> Self {
+        Self {
+            damage: 0.0, // The well only pulls, other weapons do the killing
+            speed: 0.0,  // Not used, the well stays where it was placed
+            radius: 120.0,
+            width: 0.0,  // Not used for gravity well
+            height: 0.0, // Not used for gravity well
+            time_to_live: 3.0,
+            turning_rate: 0.0, // Not used for gravity well
+            pulse_expands: false,
+            slow: SlowConfig::default(),
+            falloff: 0.0,
+            pull_strength: 30.0,
+        }
+    }
+}
+
+/// The single mapping from a projectile type to its default stats
 impl From<ProjectileType> for ProjectileStats {
     fn from(projectile_type: ProjectileType) -> Self {
         match projectile_type {
-            ProjectileType::EnergyBall => Self {
-                damage: 10.0,
-                speed: 300.0,
-                radius: 8.0,
-                width: 0.0,  // Not used for energy ball
-                height: 0.0, // Not used for energy ball
-                time_to_live: 2.0,
-                turning_rate: 0.0, // Not used for energy ball
-            },
-            ProjectileType::Pulse => Self {
-                damage: 15.0,
-                speed: 0.0,  // Not used for pulse
-                radius: 0.0, // Not used for pulse
-                width: 100.0,
-                height: 100.0,
-                time_to_live: 0.3,
-                turning_rate: 0.0, // Not used for pulse
-            },
-            ProjectileType::HomingMissile => Self {
-                damage: 20.0,
-                speed: 250.0,
-                radius: 6.0,
-                width: 0.0,  // Not used for homing missile
-                height: 0.0, // Not used for homing missile
-                time_to_live: 3.0,
-                turning_rate: 3.0, // 3 radians per second turning rate
-            },
+            ProjectileType::EnergyBall => Self::energy_ball_default(),
+            ProjectileType::Pulse => Self::pulse_default(),
+            ProjectileType::HomingMissile => Self::homing_missile_default(),
+            ProjectileType::GravityWell => Self::gravity_well_default(),
         }
     }
 }
 
+// Saves from before projectiles had an owner only contain projectiles of the player
+fn default_owner() -> EntityId {
+    PLAYER_ID
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Projectile {
     pub id: EntityId,
+    #[serde(with = "crate::serde_compat::vec2")]
     pub pos: Vec2,
+    #[serde(with = "crate::serde_compat::vec2")]
+    pub prev_pos: Vec2, // Position at the start of the logic step, for render interpolation
+    #[serde(with = "crate::serde_compat::vec2")]
     pub vel: Vec2,
     pub projectile_type: ProjectileType,
     pub stats: ProjectileStats,
     pub time_remaining: f32,
+    #[serde(with = "crate::serde_compat::vec2")]
     pub source_pos: Vec2, // Origin position (useful for pulse)
     pub visual_config: ProjectileVisualConfig,
+    pub weapon_type: WeaponType, // The weapon that fired the projectile, used for kill attribution
+    #[serde(default = "default_owner")]
+    pub owner: EntityId, // The player that fired the projectile
+    pub hit_enemies: Vec<EntityId>, // Enemies already damaged, so lingering pulses hit only once
+    #[serde(with = "crate::serde_compat::vec2")]
+    pub pulse_size: Vec2, // Current width and height of a pulse
+    pub pulse_blend: BlendConfig, // Fill colors of a pulse over its lifetime
+    #[serde(default)]
+    pub hostile: bool, // Fired by an enemy, hits the player and never the enemies
 }
 
 impl Projectile {
+    /// Width and height of a pulse, expanding pulses grow towards the full size over their lifetime
+    pub fn pulse_size_at(stats: &ProjectileStats, time_remaining: f32) -> Vec2 {
+        let full_size = Vec2::new(stats.width, stats.height);
+        if !stats.pulse_expands || stats.time_to_live <= 0.0 {
+            return full_size;
+        }
+
+        let progress = (1.0 - time_remaining / stats.time_to_live).clamp(0.0, 1.0);
+        full_size * (MIN_PULSE_SCALE + (1.0 - MIN_PULSE_SCALE) * progress)
+    }
+
     pub fn update(&mut self, dt: f32) {
         self.time_remaining -= dt;
 
@@ -78,29 +169,34 @@ impl Projectile {
             ProjectileType::Pulse => {
                 // Pulse stays at source position, doesn't move
                 self.pos = self.source_pos;
+                self.pulse_size = Self::pulse_size_at(&self.stats, self.time_remaining);
             }
             ProjectileType::HomingMissile => {
                 self.pos += self.vel * dt;
                 // Homing behavior is handled separately via update_homing
             }
+            ProjectileType::GravityWell => {
+                // The well stays where it was placed, the pull is applied via pull_on
+            }
         }
     }
 
+    /// Velocity a gravity well adds during `dt` to an enemy at `pos`, zero outside its radius
+    pub fn pull_on(&self, pos: Vec2, dt: f32) -> Vec2 {
+        if self.projectile_type != ProjectileType::GravityWell
+            || pos.distance(self.pos) > self.stats.radius
+        {
+            return Vec2::ZERO;
+        }
+        (self.pos - pos).normalize_or_zero() * self.stats.pull_strength * dt
+    }
+
     pub fn update_homing(&mut self, dt: f32, enemies: &[crate::enemy::Enemy]) {
         if self.projectile_type != ProjectileType::HomingMissile {
             return;
         }
 
-        // Find nearest enemy
-        let nearest_enemy = enemies.iter().min_by(|a, b| {
-            let dist_a = (a.pos - self.pos).length_squared();
-            let dist_b = (b.pos - self.pos).length_squared();
-            dist_a
-                .partial_cmp(&dist_b)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        if let Some(target) = nearest_enemy {
+        if let Some(target) = nearest_enemy(self.pos, enemies) {
             let to_target = (target.pos - self.pos).normalize();
             let current_dir = self.vel.normalize();
 
@@ -125,40 +221,60 @@ impl Projectile {
         }
     }
 
+    /// Enemy projectiles always hit, projectiles never hit the player that fired them,
+    /// other players only with friendly fire
+    pub fn can_hit_player(&self, player_id: EntityId, friendly_fire: bool) -> bool {
+        self.hostile || (friendly_fire && self.owner != player_id)
+    }
+
+    pub fn has_hit(&self, enemy_id: EntityId) -> bool {
+        self.hit_enemies.contains(&enemy_id)
+    }
+
     pub fn is_expired(&self) -> bool {
         self.time_remaining <= 0.0
     }
 
-    pub fn draw(&self) {
+    pub fn draw(&self, alpha: f32) {
+        let pos = self.prev_pos.lerp(self.pos, alpha);
         match self.projectile_type {
             ProjectileType::EnergyBall => {
                 draw_circle(
-                    self.pos.x,
-                    self.pos.y,
+                    pos.x,
+                    pos.y,
                     self.stats.radius,
                     self.visual_config.primary_color.to_color(),
                 );
             }
             ProjectileType::Pulse => {
-                // Draw semi-transparent rectangle for pulse with fade
-                let alpha = (self.time_remaining / self.stats.time_to_live).clamp(0.0, 1.0);
-                let mut fill_color = self.visual_config.primary_color;
-                fill_color.a *= alpha;
+                // The pulse goes from the bright inner to the dark outer blend color over its
+                // lifetime, which also fades it since the outer color is nearly transparent
+                let t = 1.0 - (self.time_remaining / self.stats.time_to_live).clamp(0.0, 1.0);
+                let size = self.pulse_size;
+                draw_rectangle(
+                    pos.x - size.x / 2.0,
+                    pos.y - size.y / 2.0,
+                    size.x,
+                    size.y,
+                    self.pulse_blend.blend(t).to_color(),
+                );
 
+                // A brighter core half a lifetime behind, so the shockwave looks layered
+                let core = size * 0.5;
                 draw_rectangle(
-                    self.pos.x - self.stats.width / 2.0,
-                    self.pos.y - self.stats.height / 2.0,
-                    self.stats.width,
-                    self.stats.height,
-                    fill_color.to_color(),
+                    pos.x - core.x / 2.0,
+                    pos.y - core.y / 2.0,
+                    core.x,
+                    core.y,
+                    self.pulse_blend.blend(t * 0.5).to_color(),
                 );
 
                 // Draw outline
                 draw_rectangle_lines(
-                    self.pos.x - self.stats.width / 2.0,
-                    self.pos.y - self.stats.height / 2.0,
-                    self.stats.width,
-                    self.stats.height,
+                    pos.x - size.x / 2.0,
+                    pos.y - size.y / 2.0,
+                    size.x,
+                    size.y,
                     2.0,
                     self.visual_config.secondary_color.to_color(),
                 );
@@ -166,38 +282,79 @@ impl Projectile {
             ProjectileType::HomingMissile => {
                 // Draw circle for homing missile
                 draw_circle(
-                    self.pos.x,
-                    self.pos.y,
+                    pos.x,
+                    pos.y,
                     self.stats.radius,
                     self.visual_config.primary_color.to_color(),
                 );
 
                 // Draw direction indicator (small triangle pointing in velocity direction)
                 draw_direction_indicator(
-                    self.pos,
+                    pos,
                     self.vel,
                     self.stats.radius,
                     self.visual_config.indicator_color,
                     2.0,
                 );
             }
+            ProjectileType::GravityWell => {
+                let fade = (self.time_remaining / self.stats.time_to_live).clamp(0.0, 1.0);
+                let mut edge_color = self.visual_config.secondary_color.to_color();
+                edge_color.a *= fade;
+                draw_circle_lines(pos.x, pos.y, self.stats.radius, 1.0, edge_color);
+
+                // Three spiral arms of dots that turn while the well is active
+                let mut swirl_color = self.visual_config.primary_color.to_color();
+                swirl_color.a *= fade;
+                let spin = (self.stats.time_to_live - self.time_remaining) * GRAVITY_WELL_SPIN;
+                for arm in 0..3 {
+                    let arm_angle = spin + arm as f32 * std::f32::consts::TAU / 3.0;
+                    for step in 0..8 {
+                        let t = step as f32 / 8.0;
+                        let angle = arm_angle + t * std::f32::consts::PI;
+                        let dot = pos + Vec2::from_angle(angle) * self.stats.radius * (1.0 - t);
+                        draw_circle(dot.x, dot.y, 1.5 + 2.5 * t, swirl_color);
+                    }
+                }
+                draw_circle(
+                    pos.x,
+                    pos.y,
+                    6.0,
+                    self.visual_config.indicator_color.to_color(),
+                );
+            }
         }
     }
 
     pub fn damage(&self) -> f32 {
         self.stats.damage
     }
+
+    /// Damage dealt to an enemy at `pos`, pulses lose `falloff` of it towards their edge
+    pub fn damage_at(&self, pos: Vec2) -> f32 {
+        if self.projectile_type != ProjectileType::Pulse || self.stats.falloff <= 0.0 {
+            return self.damage();
+        }
+        let half_extent = self.stats.width.max(self.stats.height) / 2.0;
+        if half_extent <= 0.0 {
+            return self.damage();
+        }
+        let t = (pos.distance(self.source_pos) / half_extent).clamp(0.0, 1.0);
+        self.damage() * (1.0 - self.stats.falloff.clamp(0.0, 1.0) * t)
+    }
 }
 
 impl Collidable for Projectile {
     fn collider(&self) -> Collider {
         match self.projectile_type {
-            ProjectileType::EnergyBall | ProjectileType::HomingMissile => Collider::Circle {
+            ProjectileType::EnergyBall
+            | ProjectileType::HomingMissile
+            | ProjectileType::GravityWell => Collider::Circle {
                 radius: self.stats.radius,
             },
             ProjectileType::Pulse => Collider::Rect {
-                width: self.stats.width,
-                height: self.stats.height,
+                width: self.pulse_size.x,
+                height: self.pulse_size.y,
             },
         }
     }
@@ -206,3 +363,292 @@ impl Collidable for Projectile {
         self.pos
     }
 }
+
+/// Storage for projectiles that reuses the slots of despawned projectiles instead of
+/// shifting the whole list on every despawn
+pub struct ProjectilePool {
+    slots: Vec<Projectile>,
+    active: Vec<bool>,
+    free: Vec<usize>,
+}
+
+impl ProjectilePool {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            active: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    pub fn spawn(&mut self, mut projectile: Projectile) {
+        if let Some(index) = self.free.pop() {
+            // Keep the hit list allocation of the previous occupant
+            let mut hit_enemies = std::mem::take(&mut self.slots[index].hit_enemies);
+            hit_enemies.clear();
+            projectile.hit_enemies = hit_enemies;
+
+            self.slots[index] = projectile;
+            self.active[index] = true;
+        } else {
+            self.slots.push(projectile);
+            self.active.push(true);
+        }
+    }
+
+    /// Marks the slots of the given projectiles as free for reuse
+    pub fn despawn(&mut self, ids: &BTreeSet<EntityId>) {
+        for (index, projectile) in self.slots.iter().enumerate() {
+            if self.active[index] && ids.contains(&projectile.id) {
+                self.active[index] = false;
+                self.free.push(index);
+            }
+        }
+    }
+
+    /// Applies the replace policy to the projectiles fired by the replaced `weapon_type`
+    pub fn on_weapon_replaced(&mut self, weapon_type: WeaponType, policy: ReplacedProjectiles) {
+        if policy == ReplacedProjectiles::Clear {
+            let ids: BTreeSet<EntityId> = self
+                .iter()
+                .filter(|p| !p.hostile && p.weapon_type == weapon_type)
+                .map(|p| p.id)
+                .collect();
+            self.despawn(&ids);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Projectile> {
+        self.slots
+            .iter()
+            .zip(self.active.iter())
+            .filter(|(_, active)| **active)
+            .map(|(projectile, _)| projectile)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Projectile> {
+        self.slots
+            .iter_mut()
+            .zip(self.active.iter())
+            .filter(|(_, active)| **active)
+            .map(|(projectile, _)| projectile)
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    #[cfg(test)]
+    pub fn num_slots(&self) -> usize {
+        self.slots.len()
+    }
+}
+
+/// Test fixture shared by the tests of every module
+#[cfg(test)]
+impl Projectile {
+    /// A resting projectile of the player with the default stats of its type
+    pub fn for_test(id: EntityId, projectile_type: ProjectileType, pos: Vec2) -> Self {
+        let stats = ProjectileStats::from(projectile_type);
+        let weapon_type = match projectile_type {
+            ProjectileType::EnergyBall => WeaponType::EnergyBall,
+            ProjectileType::Pulse => WeaponType::Pulse,
+            ProjectileType::HomingMissile => WeaponType::HomingMissile,
+            ProjectileType::GravityWell => WeaponType::GravityWell,
+        };
+        Projectile {
+            id,
+            pos,
+            prev_pos: pos,
+            vel: Vec2::ZERO,
+            projectile_type,
+            stats,
+            time_remaining: stats.time_to_live,
+            source_pos: pos,
+            visual_config: ProjectileVisualConfig::from(projectile_type),
+            weapon_type,
+            owner: PLAYER_ID,
+            hit_enemies: Vec::new(),
+            pulse_size: Projectile::pulse_size_at(&stats, stats.time_to_live),
+            pulse_blend: BlendConfig::pulse_default(),
+            hostile: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn expanding_pulse() -> Projectile {
+        pulse_with_id(0)
+    }
+
+    fn pulse_with_id(id: EntityId) -> Projectile {
+        let mut pulse = Projectile::for_test(id, ProjectileType::Pulse, Vec2::ZERO);
+        pulse.stats.pulse_expands = true;
+        pulse.pulse_size = Projectile::pulse_size_at(&pulse.stats, pulse.time_remaining);
+        pulse
+    }
+
+    fn collider_size(projectile: &Projectile) -> Vec2 {
+        match projectile.collider() {
+            Collider::Rect { width, height } => Vec2::new(width, height),
+            Collider::Circle { .. } => panic!("pulse should have a rect collider"),
+        }
+    }
+
+    #[test]
+    fn test_expanding_pulse_collider_grows() {
+        let mut pulse = expanding_pulse();
+        let mut prev_size = collider_size(&pulse);
+        assert!(prev_size.x < pulse.stats.width);
+
+        while !pulse.is_expired() {
+            pulse.update(0.05);
+            let size = collider_size(&pulse);
+            assert!(size.x >= prev_size.x && size.y >= prev_size.y);
+            prev_size = size;
+        }
+        assert_eq!(prev_size, Vec2::new(pulse.stats.width, pulse.stats.height));
+    }
+
+    #[test]
+    fn test_pool_reuses_slots_with_unique_ids() {
+        let mut pool = ProjectilePool::new();
+        let mut next_id = 0;
+
+        for _ in 0..10 {
+            let mut spawned = BTreeSet::new();
+            for _ in 0..50 {
+                pool.spawn(pulse_with_id(next_id));
+                spawned.insert(next_id);
+                next_id += 1;
+            }
+
+            // Every active projectile is one of this round, each id exactly once
+            let ids: Vec<EntityId> = pool.iter().map(|p| p.id).collect();
+            assert_eq!(ids.len(), 50);
+            assert_eq!(ids.iter().collect::<HashSet<_>>().len(), 50);
+            assert!(ids.iter().all(|id| spawned.contains(id)));
+
+            pool.despawn(&spawned);
+            assert_eq!(pool.len(), 0);
+        }
+
+        // Ten rounds of 50 projectiles never needed more than 50 slots
+        assert_eq!(pool.num_slots(), 50);
+    }
+
+    #[test]
+    fn test_weapon_replace_policies() {
+        for policy in [ReplacedProjectiles::Keep, ReplacedProjectiles::Clear] {
+            let mut pool = ProjectilePool::new();
+            for id in 0..3 {
+                pool.spawn(pulse_with_id(id));
+            }
+            pool.spawn(Projectile {
+                weapon_type: WeaponType::EnergyBall,
+                ..pulse_with_id(3)
+            });
+
+            pool.on_weapon_replaced(WeaponType::Pulse, policy);
+
+            let ids: Vec<EntityId> = pool.iter().map(|p| p.id).collect();
+            match policy {
+                ReplacedProjectiles::Keep => assert_eq!(ids, vec![0, 1, 2, 3]),
+                ReplacedProjectiles::Clear => assert_eq!(ids, vec![3]),
+            }
+        }
+    }
+
+    #[test]
+    fn test_pulse_falloff_hurts_less_at_the_edge() {
+        let mut pulse = pulse_with_id(0);
+        let center = pulse.damage_at(Vec2::ZERO);
+        let edge = pulse.damage_at(Vec2::new(pulse.stats.width / 2.0, 0.0));
+        // No falloff by default
+        assert_eq!(center, edge);
+
+        pulse.stats.falloff = 0.5;
+        let center = pulse.damage_at(Vec2::ZERO);
+        let edge = pulse.damage_at(Vec2::new(pulse.stats.width / 2.0, 0.0));
+        assert_eq!(center, pulse.stats.damage);
+        assert!(edge < center);
+        assert!((edge - pulse.stats.damage * 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_gravity_well_pulls_enemies_within_radius() {
+        let well = Projectile::for_test(0, ProjectileType::GravityWell, Vec2::ZERO);
+        let stats = well.stats;
+
+        // An enemy inside the radius gains velocity towards the center
+        let inside = Vec2::new(stats.radius / 2.0, stats.radius / 4.0);
+        let mut vel = Vec2::ZERO;
+        vel += well.pull_on(inside, 1.0 / 30.0);
+        assert!(vel.length() > 0.0);
+        assert!(vel.normalize().dot((well.pos - inside).normalize()) > 0.999);
+
+        // One outside is left alone
+        let outside = Vec2::new(stats.radius + 1.0, 0.0);
+        assert_eq!(well.pull_on(outside, 1.0 / 30.0), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_friendly_fire_off_spares_other_players() {
+        let (owner, other) = (1, 2);
+        let shot = Projectile {
+            owner,
+            ..pulse_with_id(0)
+        };
+
+        assert!(!shot.can_hit_player(other, false));
+        assert!(shot.can_hit_player(other, true));
+        // Nobody is hit by their own projectiles
+        assert!(!shot.can_hit_player(owner, true));
+
+        // Enemy projectiles hit without friendly fire
+        let shard = Projectile {
+            hostile: true,
+            ..pulse_with_id(0)
+        };
+        assert!(shard.can_hit_player(owner, false));
+    }
+
+    #[test]
+    fn test_from_matches_named_defaults() {
+        let pairs = [
+            (
+                ProjectileType::EnergyBall,
+                ProjectileStats::energy_ball_default(),
+            ),
+            (ProjectileType::Pulse, ProjectileStats::pulse_default()),
+            (
+                ProjectileType::HomingMissile,
+                ProjectileStats::homing_missile_default(),
+            ),
+            (
+                ProjectileType::GravityWell,
+                ProjectileStats::gravity_well_default(),
+            ),
+        ];
+        for (projectile_type, named_default) in pairs {
+            assert_eq!(ProjectileStats::from(projectile_type), named_default);
+        }
+    }
+
+    #[test]
+    fn test_stats_from_old_saves_load() {
+        // Saves from before these fields existed lack them
+        let mut json = serde_json::to_value(ProjectileStats::from(ProjectileType::Pulse)).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("falloff");
+        fields.remove("pull_strength");
+        let stats: ProjectileStats = serde_json::from_value(json).unwrap();
+        assert_eq!(stats.falloff, 0.0);
+        assert_eq!(stats.pull_strength, 0.0);
+    }
+}
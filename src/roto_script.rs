@@ -1,16 +1,64 @@
 use roto::{Runtime, Val, library};
 
-use crate::enemy::EnemyType;
-use crate::entity::EntityStats;
+use crate::barrier::BarrierConfig;
+use crate::enemy::{BerserkerRamp, ChaserReassess, Enemy, EnemyType, ShardConfig};
+use crate::entity::{DEFAULT_MAX_HEALTH, EntityStats};
+use crate::player::{AntiCampConfig, WaveRewards};
+use crate::projectile::ProjectileType;
 use crate::visual_config::{
     BlendConfig, ColorConfig, EnemyVisualConfig, GameVisualConfig, PlayerVisualConfig,
     ProjectileVisualConfig,
 };
+use crate::weapon::{
+    AdaptiveConfig, ChargeConfig, FireMode, GravityWellConfig, MasteryConfig, OverheatConfig,
+    ReplacedProjectiles, SlowConfig, TetherConfig, WeaponTuning,
+};
+
+/// Whether a script package defines the named function with the expected signature
+type SignatureCheck = fn(&mut roto::Package, &str) -> bool;
+
+/// Calls the named function with the console arguments and formats its result
+type ConsoleCall = fn(&mut RotoScriptManager, &str, &[&str]) -> Result<String, String>;
+
+/// A Roto function that can be called from the debug console
+struct ConsoleFunction {
+    name: &'static str,
+    args: usize, // Arguments the console has to pass
+    is_defined: SignatureCheck,
+    call: ConsoleCall,
+}
 
 #[derive(Clone, Copy, Debug)]
 pub struct WaveConfig {
     pub basic_enemy_count: u32,
     pub chaser_enemy_count: u32,
+    pub teleporter_enemy_count: u32,
+    pub berserker_enemy_count: u32,
+    pub phaser_enemy_count: u32,
+    pub builder_enemy_count: u32,
+    pub glass_enemy_count: u32,
+}
+
+/// What happens at the screen edges
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BoundsMode {
+    Deadly, // Leaving the screen ends the run, enemies and projectiles despawn
+    Wrap,   // Everything re-enters on the opposite edge
+}
+
+/// What happens to an enemy that left the screen by more than the margin, in a deadly world
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EnemyBounds {
+    Despawn, // Removed, the player gets no XP for it
+    Bounce,  // Reflected back into the arena
+    Wrap,    // Re-enters on the opposite edge
+}
+
+/// How the player is protected after an enemy contact
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ContactModel {
+    Global,   // The enemy is destroyed, then the player's i-frames block every other hit
+    PerEnemy, // The enemy stays and hits again after its own cooldown, crowds hurt more
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -18,10 +66,30 @@ pub struct GameConstants {
     pub out_of_bounds_margin: f32,
     pub spawn_target_offset: f32,
     pub max_waves: u32,
+    pub bounds_mode: BoundsMode,
+    pub enemy_bounds: EnemyBounds,
+    pub graze_margin: f32, // Enemies passing within this distance of the player count as a graze
+    pub graze_bonus: u32,  // XP awarded per graze
+    pub spawn_telegraph_time: f32, // Seconds a spawn marker is shown before the enemy appears
+    pub contact_damage: f32, // Damage the player takes when touching an enemy
+    pub contact_model: ContactModel,
+    pub contact_cooldown: f32, // Seconds between two hits of the same enemy, per-enemy model only
+    pub max_enemies: u32,      // Live enemy cap, further spawns wait until there is room
+    pub knockback: f32,        // Speed the player is pushed away on contact, capped at max speed
+    pub replaced_projectiles: ReplacedProjectiles, // In-flight projectiles of a replaced weapon
+    pub shield_drop_chance: f32, // Chance from 0.0 to 1.0 that a killed enemy drops a shield
+    pub tick_rate: u32,        // Logic updates per second
+    pub friendly_fire: bool,   // Whether projectiles of one player can hit another player
+    pub wave_rewards: WaveRewards, // Rewards to pick from between two waves
+    pub anti_camp: AntiCampConfig, // Enemies spawned around a player who stands still
 }
 
+/// Script loaded when no `--script` argument is given
+pub const DEFAULT_SCRIPT_PATH: &str = "scripts/main.roto";
+
 pub struct RotoScriptManager {
     runtime: Runtime,
+    script_path: String,
 }
 
 impl RotoScriptManager {
@@ -30,28 +98,193 @@ impl RotoScriptManager {
             #[copy] type EntityStats = Val<EntityStats>;
             #[copy] type WaveComposition = Val<WaveConfig>;
             #[copy] type GameConstants = Val<GameConstants>;
+            #[copy] type BoundsMode = Val<BoundsMode>;
+            #[copy] type EnemyBounds = Val<EnemyBounds>;
+            #[copy] type ContactModel = Val<ContactModel>;
+            #[copy] type ReplacedProjectiles = Val<ReplacedProjectiles>;
             #[copy] type ColorConfig = Val<ColorConfig>;
             #[copy] type PlayerVisualConfig = Val<PlayerVisualConfig>;
             #[copy] type EnemyVisualConfig = Val<EnemyVisualConfig>;
             #[copy] type ProjectileVisualConfig = Val<ProjectileVisualConfig>;
             #[copy] type BlendConfig = Val<BlendConfig>;
             #[clone] type GameVisualConfig = Val<GameVisualConfig>;
+            #[copy] type ChargeConfig = Val<ChargeConfig>;
+            #[copy] type SlowConfig = Val<SlowConfig>;
+            #[copy] type OverheatConfig = Val<OverheatConfig>;
+            #[copy] type GravityWellConfig = Val<GravityWellConfig>;
+            #[copy] type AdaptiveConfig = Val<AdaptiveConfig>;
+            #[copy] type MasteryConfig = Val<MasteryConfig>;
+            #[copy] type TetherConfig = Val<TetherConfig>;
+            #[copy] type BerserkerRamp = Val<BerserkerRamp>;
+            #[copy] type ChaserReassess = Val<ChaserReassess>;
+            #[copy] type BarrierConfig = Val<BarrierConfig>;
+            #[copy] type ShardConfig = Val<ShardConfig>;
 
             impl Val<EntityStats> {
                 fn new(radius: f32, max_speed: f32, acceleration: f32, friction: f32) -> Val<EntityStats> {
-                    Val(EntityStats { radius, max_speed, acceleration, friction })
+                    Val(EntityStats { radius, max_speed, acceleration, friction, max_health: DEFAULT_MAX_HEALTH })
+                }
+                fn with_health(stats: Val<EntityStats>, max_health: f32) -> Val<EntityStats> {
+                    Val(EntityStats { max_health, ..stats.0 })
                 }
             }
 
             impl Val<WaveConfig> {
                 fn new(basic_count: u32, chaser_count: u32) -> Val<WaveConfig> {
-                    Val(WaveConfig { basic_enemy_count: basic_count, chaser_enemy_count: chaser_count })
+                    Val(WaveConfig { basic_enemy_count: basic_count, chaser_enemy_count: chaser_count, teleporter_enemy_count: 0, berserker_enemy_count: 0, phaser_enemy_count: 0, builder_enemy_count: 0, glass_enemy_count: 0 })
+                }
+                fn with_teleporters(wave: Val<WaveConfig>, teleporter_count: u32) -> Val<WaveConfig> {
+                    Val(WaveConfig { teleporter_enemy_count: teleporter_count, ..wave.0 })
+                }
+                fn with_berserkers(wave: Val<WaveConfig>, berserker_count: u32) -> Val<WaveConfig> {
+                    Val(WaveConfig { berserker_enemy_count: berserker_count, ..wave.0 })
+                }
+                fn with_phasers(wave: Val<WaveConfig>, phaser_count: u32) -> Val<WaveConfig> {
+                    Val(WaveConfig { phaser_enemy_count: phaser_count, ..wave.0 })
+                }
+                fn with_builders(wave: Val<WaveConfig>, builder_count: u32) -> Val<WaveConfig> {
+                    Val(WaveConfig { builder_enemy_count: builder_count, ..wave.0 })
+                }
+                fn with_glass(wave: Val<WaveConfig>, glass_count: u32) -> Val<WaveConfig> {
+                    Val(WaveConfig { glass_enemy_count: glass_count, ..wave.0 })
                 }
             }
 
             impl Val<GameConstants> {
                 fn new(out_of_bounds_margin: f32, spawn_target_offset: f32, max_waves: u32) -> Val<GameConstants> {
-                    Val(GameConstants { out_of_bounds_margin, spawn_target_offset, max_waves })
+                    Val(GameConstants { out_of_bounds_margin, spawn_target_offset, max_waves, bounds_mode: BoundsMode::Deadly, enemy_bounds: EnemyBounds::Despawn, graze_margin: 10.0, graze_bonus: 1, spawn_telegraph_time: 0.5, contact_damage: DEFAULT_MAX_HEALTH, contact_model: ContactModel::Global, contact_cooldown: 1.0, max_enemies: 60, knockback: 5.0, replaced_projectiles: ReplacedProjectiles::Keep, shield_drop_chance: 0.0, tick_rate: crate::DEFAULT_TICK_RATE, friendly_fire: false, wave_rewards: WaveRewards::default(), anti_camp: AntiCampConfig::default() })
+                }
+                fn with_graze(constants: Val<GameConstants>, graze_margin: f32, graze_bonus: u32) -> Val<GameConstants> {
+                    Val(GameConstants { graze_margin, graze_bonus, ..constants.0 })
+                }
+                fn with_spawn_telegraph(constants: Val<GameConstants>, spawn_telegraph_time: f32) -> Val<GameConstants> {
+                    Val(GameConstants { spawn_telegraph_time, ..constants.0 })
+                }
+                fn with_contact_damage(constants: Val<GameConstants>, contact_damage: f32) -> Val<GameConstants> {
+                    Val(GameConstants { contact_damage, ..constants.0 })
+                }
+                fn with_contact_model(constants: Val<GameConstants>, contact_model: Val<ContactModel>, contact_cooldown: f32) -> Val<GameConstants> {
+                    Val(GameConstants { contact_model: contact_model.0, contact_cooldown, ..constants.0 })
+                }
+                fn with_max_enemies(constants: Val<GameConstants>, max_enemies: u32) -> Val<GameConstants> {
+                    Val(GameConstants { max_enemies, ..constants.0 })
+                }
+                fn with_knockback(constants: Val<GameConstants>, knockback: f32) -> Val<GameConstants> {
+                    Val(GameConstants { knockback, ..constants.0 })
+                }
+                fn with_bounds_mode(constants: Val<GameConstants>, bounds_mode: Val<BoundsMode>) -> Val<GameConstants> {
+                    Val(GameConstants { bounds_mode: bounds_mode.0, ..constants.0 })
+                }
+                fn with_enemy_bounds(constants: Val<GameConstants>, enemy_bounds: Val<EnemyBounds>) -> Val<GameConstants> {
+                    Val(GameConstants { enemy_bounds: enemy_bounds.0, ..constants.0 })
+                }
+                fn with_replaced_projectiles(constants: Val<GameConstants>, replaced_projectiles: Val<ReplacedProjectiles>) -> Val<GameConstants> {
+                    Val(GameConstants { replaced_projectiles: replaced_projectiles.0, ..constants.0 })
+                }
+                fn with_friendly_fire(constants: Val<GameConstants>, friendly_fire: bool) -> Val<GameConstants> {
+                    Val(GameConstants { friendly_fire, ..constants.0 })
+                }
+                fn with_tick_rate(constants: Val<GameConstants>, tick_rate: u32) -> Val<GameConstants> {
+                    Val(GameConstants { tick_rate, ..constants.0 })
+                }
+                fn with_shield_drop_chance(constants: Val<GameConstants>, shield_drop_chance: f32) -> Val<GameConstants> {
+                    Val(GameConstants { shield_drop_chance, ..constants.0 })
+                }
+                fn with_wave_rewards(constants: Val<GameConstants>, heal: f32, buff_multiplier: f32, buff_duration: f32, bonus_xp: u32) -> Val<GameConstants> {
+                    Val(GameConstants { wave_rewards: WaveRewards { heal, buff_multiplier, buff_duration, bonus_xp }, ..constants.0 })
+                }
+                fn with_anti_camp(constants: Val<GameConstants>, speed_threshold: f32, time_limit: f32, enemy_count: u32, distance: f32) -> Val<GameConstants> {
+                    Val(GameConstants { anti_camp: AntiCampConfig { speed_threshold, time_limit, enemy_count, distance }, ..constants.0 })
+                }
+            }
+
+            impl Val<BoundsMode> {
+                fn deadly() -> Val<BoundsMode> { Val(BoundsMode::Deadly) }
+                fn wrap() -> Val<BoundsMode> { Val(BoundsMode::Wrap) }
+            }
+
+            impl Val<EnemyBounds> {
+                fn despawn() -> Val<EnemyBounds> { Val(EnemyBounds::Despawn) }
+                fn bounce() -> Val<EnemyBounds> { Val(EnemyBounds::Bounce) }
+                fn wrap() -> Val<EnemyBounds> { Val(EnemyBounds::Wrap) }
+            }
+
+            impl Val<ContactModel> {
+                fn global() -> Val<ContactModel> { Val(ContactModel::Global) }
+                fn per_enemy() -> Val<ContactModel> { Val(ContactModel::PerEnemy) }
+            }
+
+            impl Val<ReplacedProjectiles> {
+                fn keep() -> Val<ReplacedProjectiles> { Val(ReplacedProjectiles::Keep) }
+                fn clear() -> Val<ReplacedProjectiles> { Val(ReplacedProjectiles::Clear) }
+            }
+
+            impl Val<ChargeConfig> {
+                fn new(max_charge_time: f32, damage_scale: f32, size_scale: f32) -> Val<ChargeConfig> {
+                    Val(ChargeConfig { max_charge_time, damage_scale, size_scale })
+                }
+            }
+
+            impl Val<BerserkerRamp> {
+                fn new(start_distance: f32, max_multiplier: f32) -> Val<BerserkerRamp> {
+                    Val(BerserkerRamp { start_distance, max_multiplier })
+                }
+            }
+
+            impl Val<ChaserReassess> {
+                fn new(interval: f32, max_angle: f32) -> Val<ChaserReassess> {
+                    Val(ChaserReassess { interval, max_angle })
+                }
+            }
+
+            impl Val<BarrierConfig> {
+                fn new(length: f32, thickness: f32, duration: f32, interval: f32) -> Val<BarrierConfig> {
+                    Val(BarrierConfig { length, thickness, duration, interval })
+                }
+            }
+
+            impl Val<ShardConfig> {
+                fn new(count: u32, damage: f32, speed: f32, radius: f32, time_to_live: f32, telegraph_time: f32) -> Val<ShardConfig> {
+                    Val(ShardConfig { count, damage, speed, radius, time_to_live, telegraph_time })
+                }
+            }
+
+            impl Val<OverheatConfig> {
+                fn cooldown() -> Val<OverheatConfig> {
+                    Val(OverheatConfig::default())
+                }
+                fn overheat(max_heat: f32, heat_per_shot: f32, cooldown_rate: f32) -> Val<OverheatConfig> {
+                    Val(OverheatConfig { fire_mode: FireMode::Overheat, max_heat, heat_per_shot, cooldown_rate })
+                }
+            }
+
+            impl Val<GravityWellConfig> {
+                fn new(radius: f32, pull_strength: f32) -> Val<GravityWellConfig> {
+                    Val(GravityWellConfig { radius, pull_strength })
+                }
+            }
+
+            impl Val<AdaptiveConfig> {
+                fn new(radius: f32, threshold: u32) -> Val<AdaptiveConfig> {
+                    Val(AdaptiveConfig { radius, threshold })
+                }
+            }
+
+            impl Val<MasteryConfig> {
+                fn new(kills_per_tier: u32, damage_per_tier: f32) -> Val<MasteryConfig> {
+                    Val(MasteryConfig { kills_per_tier, damage_per_tier })
+                }
+            }
+
+            impl Val<TetherConfig> {
+                fn new(range: f32, dps: f32, slow_factor: f32) -> Val<TetherConfig> {
+                    Val(TetherConfig { range, dps, slow_factor })
+                }
+            }
+
+            impl Val<SlowConfig> {
+                fn new(factor: f32, duration: f32) -> Val<SlowConfig> {
+                    Val(SlowConfig { factor, duration })
                 }
             }
 
@@ -77,7 +310,10 @@ impl RotoScriptManager {
 
             impl Val<EnemyVisualConfig> {
                 fn new(circle_color: Val<ColorConfig>, indicator_color: Val<ColorConfig>, indicator_size: f32) -> Val<EnemyVisualConfig> {
-                    Val(EnemyVisualConfig { circle_color: circle_color.0, indicator_color: indicator_color.0, indicator_size })
+                    Val(EnemyVisualConfig { circle_color: circle_color.0, indicator_color: indicator_color.0, indicator_size, ..EnemyVisualConfig::basic_default() })
+                }
+                fn with_health_bar(config: Val<EnemyVisualConfig>, width: f32, height: f32, offset: f32) -> Val<EnemyVisualConfig> {
+                    Val(EnemyVisualConfig { health_bar_width: width, health_bar_height: height, health_bar_offset: offset, ..config.0 })
                 }
             }
 
@@ -107,38 +343,81 @@ impl RotoScriptManager {
                         player: player.0,
                         basic_enemy: basic_enemy.0,
                         chaser_enemy: chaser_enemy.0,
+                        teleporter_enemy: EnemyVisualConfig::teleporter_default(),
+                        berserker_enemy: EnemyVisualConfig::berserker_default(),
+                        phaser_enemy: EnemyVisualConfig::phaser_default(),
+                        builder_enemy: EnemyVisualConfig::builder_default(),
+                        glass_enemy: EnemyVisualConfig::glass_default(),
                         energy_ball: energy_ball.0,
                         pulse: pulse.0,
                         homing_missile: homing_missile.0,
+                        gravity_well: ProjectileVisualConfig::from(ProjectileType::GravityWell),
                         pulse_blend: pulse_blend.0,
+                        background: BlendConfig::background_default(),
                     })
                 }
+                fn with_teleporter_enemy(config: Val<GameVisualConfig>, teleporter_enemy: Val<EnemyVisualConfig>) -> Val<GameVisualConfig> {
+                    Val(GameVisualConfig { teleporter_enemy: teleporter_enemy.0, ..config.0 })
+                }
+                fn with_berserker_enemy(config: Val<GameVisualConfig>, berserker_enemy: Val<EnemyVisualConfig>) -> Val<GameVisualConfig> {
+                    Val(GameVisualConfig { berserker_enemy: berserker_enemy.0, ..config.0 })
+                }
+                fn with_phaser_enemy(config: Val<GameVisualConfig>, phaser_enemy: Val<EnemyVisualConfig>) -> Val<GameVisualConfig> {
+                    Val(GameVisualConfig { phaser_enemy: phaser_enemy.0, ..config.0 })
+                }
+                fn with_builder_enemy(config: Val<GameVisualConfig>, builder_enemy: Val<EnemyVisualConfig>) -> Val<GameVisualConfig> {
+                    Val(GameVisualConfig { builder_enemy: builder_enemy.0, ..config.0 })
+                }
+                fn with_glass_enemy(config: Val<GameVisualConfig>, glass_enemy: Val<EnemyVisualConfig>) -> Val<GameVisualConfig> {
+                    Val(GameVisualConfig { glass_enemy: glass_enemy.0, ..config.0 })
+                }
+                fn with_gravity_well(config: Val<GameVisualConfig>, gravity_well: Val<ProjectileVisualConfig>) -> Val<GameVisualConfig> {
+                    Val(GameVisualConfig { gravity_well: gravity_well.0, ..config.0 })
+                }
+                fn with_background(config: Val<GameVisualConfig>, background: Val<BlendConfig>) -> Val<GameVisualConfig> {
+                    Val(GameVisualConfig { background: background.0, ..config.0 })
+                }
             }
         };
 
         Runtime::from_lib(lib).unwrap()
     }
 
-    pub fn new() -> Self {
+    pub fn new(script_path: String) -> Self {
         let runtime = Self::create_runtime();
-        let mut manager = Self { runtime };
+        let mut manager = Self {
+            runtime,
+            script_path,
+        };
         manager.load_scripts();
         manager
     }
 
+    /// Manager that hasn't compiled its script yet, e.g. to stand in while the real one moves
+    pub fn unloaded(script_path: String) -> Self {
+        Self {
+            runtime: Self::create_runtime(),
+            script_path,
+        }
+    }
+
+    pub fn script_path(&self) -> &str {
+        &self.script_path
+    }
+
     fn load_scripts(&mut self) {
-        match self.runtime.compile("scripts/main.roto") {
+        match self.runtime.compile(&self.script_path) {
             Ok(_) => {
-                println!("✓ Loaded main.roto successfully");
+                println!("✓ Loaded {} successfully", self.script_path);
             }
             Err(err) => {
-                eprintln!("ERROR loading main.roto: {}", err);
+                eprintln!("ERROR loading {}: {}", self.script_path, err);
             }
         }
     }
 
     pub fn reload(&mut self) {
-        println!("Reloading main.roto...");
+        println!("Reloading {}...", self.script_path);
         self.runtime = Self::create_runtime();
         self.load_scripts();
     }
@@ -149,8 +428,8 @@ impl RotoScriptManager {
     {
         let mut pkg = self
             .runtime
-            .compile("scripts/main.roto")
-            .map_err(|err| format!("ERROR compiling main.roto: {}", err))?;
+            .compile(&self.script_path)
+            .map_err(|err| format!("ERROR compiling {}: {}", self.script_path, err))?;
 
         call(&mut pkg)
     }
@@ -168,6 +447,13 @@ impl RotoScriptManager {
         let func_name = match enemy_type {
             EnemyType::Basic => "get_basic_enemy_stats",
             EnemyType::Chaser => "get_chaser_enemy_stats",
+            EnemyType::Teleporter => "get_teleporter_enemy_stats",
+            EnemyType::Berserker => "get_berserker_enemy_stats",
+            EnemyType::Phaser => "get_phaser_enemy_stats",
+            EnemyType::Builder => "get_builder_enemy_stats",
+            EnemyType::Glass => "get_glass_enemy_stats",
+            // The sandbox dummy isn't part of the script
+            EnemyType::Dummy => return Ok(Enemy::dummy_stats()),
         };
 
         self.call_roto_function(func_name, |pkg| {
@@ -178,6 +464,46 @@ impl RotoScriptManager {
         })
     }
 
+    /// The Berserker speed-up, optional in the script
+    pub fn get_berserker_ramp(&mut self) -> Result<BerserkerRamp, String> {
+        self.call_roto_function("get_berserker_ramp", |pkg| {
+            match pkg.get_function::<(), fn() -> Val<BerserkerRamp>>("get_berserker_ramp") {
+                Ok(func) => Ok(func.call(&mut ()).0),
+                Err(_) => Ok(BerserkerRamp::default()),
+            }
+        })
+    }
+
+    /// How chasers spread around the player, optional in the script
+    pub fn get_chaser_reassess(&mut self) -> Result<ChaserReassess, String> {
+        self.call_roto_function("get_chaser_reassess", |pkg| {
+            match pkg.get_function::<(), fn() -> Val<ChaserReassess>>("get_chaser_reassess") {
+                Ok(func) => Ok(func.call(&mut ()).0),
+                Err(_) => Ok(ChaserReassess::default()),
+            }
+        })
+    }
+
+    /// The barriers Builders put down, optional in the script
+    pub fn get_builder_barrier(&mut self) -> Result<BarrierConfig, String> {
+        self.call_roto_function("get_builder_barrier", |pkg| {
+            match pkg.get_function::<(), fn() -> Val<BarrierConfig>>("get_builder_barrier") {
+                Ok(func) => Ok(func.call(&mut ()).0),
+                Err(_) => Ok(BarrierConfig::default()),
+            }
+        })
+    }
+
+    /// The shards a killed Glass enemy bursts into, optional in the script
+    pub fn get_glass_shards(&mut self) -> Result<ShardConfig, String> {
+        self.call_roto_function("get_glass_shards", |pkg| {
+            match pkg.get_function::<(), fn() -> Val<ShardConfig>>("get_glass_shards") {
+                Ok(func) => Ok(func.call(&mut ()).0),
+                Err(_) => Ok(ShardConfig::default()),
+            }
+        })
+    }
+
     pub fn get_player_stats(&mut self) -> Result<EntityStats, String> {
         self.call_roto_function("get_player_stats", |pkg| {
             let func = pkg
@@ -207,4 +533,426 @@ impl RotoScriptManager {
             }
         })
     }
+
+    pub fn get_weapon_tuning(&mut self) -> Result<WeaponTuning, String> {
+        self.call_roto_function("get_weapon_tuning", |pkg| {
+            // All weapon tuning functions are optional, missing ones keep the defaults
+            let mut tuning = WeaponTuning::default();
+            if let Ok(func) =
+                pkg.get_function::<(), fn() -> Val<ChargeConfig>>("get_cannon_charge_config")
+            {
+                tuning.charge = func.call(&mut ()).0;
+            }
+            if let Ok(func) = pkg.get_function::<(), fn() -> bool>("get_pulse_expands") {
+                tuning.pulse_expands = func.call(&mut ());
+            }
+            if let Ok(func) = pkg.get_function::<(), fn() -> Val<SlowConfig>>("get_pulse_slow") {
+                tuning.pulse_slow = func.call(&mut ()).0;
+            }
+            if let Ok(func) = pkg.get_function::<(), fn() -> f32>("get_weapon_lifesteal") {
+                tuning.lifesteal = func.call(&mut ());
+            }
+            if let Ok(func) = pkg.get_function::<(), fn() -> f32>("get_pulse_falloff") {
+                tuning.pulse_falloff = func.call(&mut ());
+            }
+            if let Ok(func) = pkg.get_function::<(), fn() -> bool>("get_energy_ball_full_circle") {
+                tuning.energy_ball_full_circle = func.call(&mut ());
+            }
+            if let Ok(func) =
+                pkg.get_function::<(), fn() -> Val<OverheatConfig>>("get_energy_ball_overheat")
+            {
+                tuning.energy_ball_overheat = func.call(&mut ()).0;
+            }
+            if let Ok(func) =
+                pkg.get_function::<(), fn() -> Val<GravityWellConfig>>("get_gravity_well")
+            {
+                tuning.gravity_well = func.call(&mut ()).0;
+            }
+            if let Ok(func) =
+                pkg.get_function::<(), fn() -> Val<AdaptiveConfig>>("get_adaptive_weapon")
+            {
+                tuning.adaptive = func.call(&mut ()).0;
+            }
+            if let Ok(func) =
+                pkg.get_function::<(), fn() -> Val<MasteryConfig>>("get_weapon_mastery")
+            {
+                tuning.mastery = func.call(&mut ()).0;
+            }
+            if let Ok(func) = pkg.get_function::<(), fn() -> Val<TetherConfig>>("get_tether") {
+                tuning.tether = func.call(&mut ()).0;
+            }
+            Ok(tuning)
+        })
+    }
+
+    /// Roto functions that can be called from the debug console
+    const CONSOLE_FUNCTIONS: [ConsoleFunction; 26] = [
+        ConsoleFunction {
+            name: "get_player_stats",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<EntityStats>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| Ok(format!("{:?}", scripts.get_player_stats()?)),
+        },
+        ConsoleFunction {
+            name: "get_basic_enemy_stats",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<EntityStats>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| Ok(format!("{:?}", scripts.get_enemy_stats(EnemyType::Basic)?)),
+        },
+        ConsoleFunction {
+            name: "get_chaser_enemy_stats",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<EntityStats>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| Ok(format!("{:?}", scripts.get_enemy_stats(EnemyType::Chaser)?)),
+        },
+        ConsoleFunction {
+            name: "get_teleporter_enemy_stats",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<EntityStats>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| {
+                Ok(format!(
+                    "{:?}",
+                    scripts.get_enemy_stats(EnemyType::Teleporter)?
+                ))
+            },
+        },
+        ConsoleFunction {
+            name: "get_berserker_enemy_stats",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<EntityStats>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| {
+                Ok(format!(
+                    "{:?}",
+                    scripts.get_enemy_stats(EnemyType::Berserker)?
+                ))
+            },
+        },
+        ConsoleFunction {
+            name: "get_berserker_ramp",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<BerserkerRamp>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| Ok(format!("{:?}", scripts.get_berserker_ramp()?)),
+        },
+        ConsoleFunction {
+            name: "get_chaser_reassess",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<ChaserReassess>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| Ok(format!("{:?}", scripts.get_chaser_reassess()?)),
+        },
+        ConsoleFunction {
+            name: "get_phaser_enemy_stats",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<EntityStats>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| Ok(format!("{:?}", scripts.get_enemy_stats(EnemyType::Phaser)?)),
+        },
+        ConsoleFunction {
+            name: "get_builder_enemy_stats",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<EntityStats>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| {
+                Ok(format!(
+                    "{:?}",
+                    scripts.get_enemy_stats(EnemyType::Builder)?
+                ))
+            },
+        },
+        ConsoleFunction {
+            name: "get_builder_barrier",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<BarrierConfig>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| Ok(format!("{:?}", scripts.get_builder_barrier()?)),
+        },
+        ConsoleFunction {
+            name: "get_glass_enemy_stats",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<EntityStats>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| Ok(format!("{:?}", scripts.get_enemy_stats(EnemyType::Glass)?)),
+        },
+        ConsoleFunction {
+            name: "get_glass_shards",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<ShardConfig>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| Ok(format!("{:?}", scripts.get_glass_shards()?)),
+        },
+        ConsoleFunction {
+            name: "get_game_constants",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<GameConstants>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| Ok(format!("{:?}", scripts.get_game_constants()?)),
+        },
+        ConsoleFunction {
+            name: "get_wave_composition",
+            args: 1,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn(u32) -> Val<WaveConfig>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, args| {
+                let wave_num = args[0]
+                    .parse::<u32>()
+                    .map_err(|_| format!("ERROR: '{}' is not a wave number", args[0]))?;
+                Ok(format!("{:?}", scripts.get_wave_config(wave_num)?))
+            },
+        },
+        ConsoleFunction {
+            name: "get_visual_config",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<GameVisualConfig>>(name)
+                    .is_ok()
+            },
+            call: |scripts, _, _| Ok(format!("{:?}", scripts.get_visual_config()?)),
+        },
+        ConsoleFunction {
+            name: "get_cannon_charge_config",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<ChargeConfig>>(name)
+                    .is_ok()
+            },
+            call: |scripts, name, _| {
+                scripts.call_roto_function(name, |pkg| {
+                    let func = pkg
+                        .get_function::<(), fn() -> Val<ChargeConfig>>(name)
+                        .map_err(|_| format!("ERROR: {} function not found", name))?;
+                    Ok(format!("{:?}", func.call(&mut ()).0))
+                })
+            },
+        },
+        ConsoleFunction {
+            name: "get_pulse_expands",
+            args: 0,
+            is_defined: |pkg, name| pkg.get_function::<(), fn() -> bool>(name).is_ok(),
+            call: |scripts, name, _| {
+                scripts.call_roto_function(name, |pkg| {
+                    let func = pkg
+                        .get_function::<(), fn() -> bool>(name)
+                        .map_err(|_| format!("ERROR: {} function not found", name))?;
+                    Ok(format!("{:?}", func.call(&mut ())))
+                })
+            },
+        },
+        ConsoleFunction {
+            name: "get_pulse_slow",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<SlowConfig>>(name)
+                    .is_ok()
+            },
+            call: |scripts, name, _| {
+                scripts.call_roto_function(name, |pkg| {
+                    let func = pkg
+                        .get_function::<(), fn() -> Val<SlowConfig>>(name)
+                        .map_err(|_| format!("ERROR: {} function not found", name))?;
+                    Ok(format!("{:?}", func.call(&mut ()).0))
+                })
+            },
+        },
+        ConsoleFunction {
+            name: "get_weapon_lifesteal",
+            args: 0,
+            is_defined: |pkg, name| pkg.get_function::<(), fn() -> f32>(name).is_ok(),
+            call: |scripts, name, _| {
+                scripts.call_roto_function(name, |pkg| {
+                    let func = pkg
+                        .get_function::<(), fn() -> f32>(name)
+                        .map_err(|_| format!("ERROR: {} function not found", name))?;
+                    Ok(format!("{:?}", func.call(&mut ())))
+                })
+            },
+        },
+        ConsoleFunction {
+            name: "get_pulse_falloff",
+            args: 0,
+            is_defined: |pkg, name| pkg.get_function::<(), fn() -> f32>(name).is_ok(),
+            call: |scripts, name, _| {
+                scripts.call_roto_function(name, |pkg| {
+                    let func = pkg
+                        .get_function::<(), fn() -> f32>(name)
+                        .map_err(|_| format!("ERROR: {} function not found", name))?;
+                    Ok(format!("{:?}", func.call(&mut ())))
+                })
+            },
+        },
+        ConsoleFunction {
+            name: "get_energy_ball_full_circle",
+            args: 0,
+            is_defined: |pkg, name| pkg.get_function::<(), fn() -> bool>(name).is_ok(),
+            call: |scripts, name, _| {
+                scripts.call_roto_function(name, |pkg| {
+                    let func = pkg
+                        .get_function::<(), fn() -> bool>(name)
+                        .map_err(|_| format!("ERROR: {} function not found", name))?;
+                    Ok(format!("{:?}", func.call(&mut ())))
+                })
+            },
+        },
+        ConsoleFunction {
+            name: "get_energy_ball_overheat",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<OverheatConfig>>(name)
+                    .is_ok()
+            },
+            call: |scripts, name, _| {
+                scripts.call_roto_function(name, |pkg| {
+                    let func = pkg
+                        .get_function::<(), fn() -> Val<OverheatConfig>>(name)
+                        .map_err(|_| format!("ERROR: {} function not found", name))?;
+                    Ok(format!("{:?}", func.call(&mut ()).0))
+                })
+            },
+        },
+        ConsoleFunction {
+            name: "get_gravity_well",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<GravityWellConfig>>(name)
+                    .is_ok()
+            },
+            call: |scripts, name, _| {
+                scripts.call_roto_function(name, |pkg| {
+                    let func = pkg
+                        .get_function::<(), fn() -> Val<GravityWellConfig>>(name)
+                        .map_err(|_| format!("ERROR: {} function not found", name))?;
+                    Ok(format!("{:?}", func.call(&mut ()).0))
+                })
+            },
+        },
+        ConsoleFunction {
+            name: "get_adaptive_weapon",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<AdaptiveConfig>>(name)
+                    .is_ok()
+            },
+            call: |scripts, name, _| {
+                scripts.call_roto_function(name, |pkg| {
+                    let func = pkg
+                        .get_function::<(), fn() -> Val<AdaptiveConfig>>(name)
+                        .map_err(|_| format!("ERROR: {} function not found", name))?;
+                    Ok(format!("{:?}", func.call(&mut ()).0))
+                })
+            },
+        },
+        ConsoleFunction {
+            name: "get_weapon_mastery",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<MasteryConfig>>(name)
+                    .is_ok()
+            },
+            call: |scripts, name, _| {
+                scripts.call_roto_function(name, |pkg| {
+                    let func = pkg
+                        .get_function::<(), fn() -> Val<MasteryConfig>>(name)
+                        .map_err(|_| format!("ERROR: {} function not found", name))?;
+                    Ok(format!("{:?}", func.call(&mut ()).0))
+                })
+            },
+        },
+        ConsoleFunction {
+            name: "get_tether",
+            args: 0,
+            is_defined: |pkg, name| {
+                pkg.get_function::<(), fn() -> Val<TetherConfig>>(name)
+                    .is_ok()
+            },
+            call: |scripts, name, _| {
+                scripts.call_roto_function(name, |pkg| {
+                    let func = pkg
+                        .get_function::<(), fn() -> Val<TetherConfig>>(name)
+                        .map_err(|_| format!("ERROR: {} function not found", name))?;
+                    Ok(format!("{:?}", func.call(&mut ()).0))
+                })
+            },
+        },
+    ];
+
+    /// Names of the Roto functions that can be called from the debug console
+    pub fn console_functions() -> impl Iterator<Item = &'static str> {
+        Self::CONSOLE_FUNCTIONS.iter().map(|function| function.name)
+    }
+
+    /// Which of the expected Roto functions the script defines with the right signature,
+    /// the others fall back to their defaults
+    pub fn function_report(&mut self) -> Result<Vec<(&'static str, bool)>, String> {
+        self.call_roto_function("function_report", |pkg| {
+            Ok(Self::CONSOLE_FUNCTIONS
+                .iter()
+                .map(|function| (function.name, (function.is_defined)(pkg, function.name)))
+                .collect())
+        })
+    }
+
+    /// Prints per expected function whether it was found or defaulted
+    pub fn print_function_report(&mut self) {
+        match self.function_report() {
+            Ok(report) => {
+                println!("Roto functions in {}:", self.script_path);
+                for (name, found) in report {
+                    let status = if found { "found" } else { "defaulted" };
+                    println!("  {:<30} {}", name, status);
+                }
+            }
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+
+    /// Calls a registered Roto function by name and formats the returned value
+    pub fn call_from_console(&mut self, name: &str, args: &[&str]) -> Result<String, String> {
+        let function = Self::CONSOLE_FUNCTIONS
+            .iter()
+            .find(|function| function.name == name)
+            .ok_or_else(|| format!("ERROR: unknown function '{}'", name))?;
+        if args.len() != function.args {
+            return Err(format!(
+                "ERROR: {} expects {} argument(s), got {}",
+                name,
+                function.args,
+                args.len()
+            ));
+        }
+
+        (function.call)(self, name, args)
+    }
 }
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::barrier::Barrier;
+use crate::enemy::{Enemy, ShardBurst};
+use crate::entity::{EntityId, PendingSpawn};
+use crate::pickup::Pickup;
+use crate::player::Player;
+use crate::projectile::Projectile;
+use crate::weapon::WeaponType;
+
+/// File the game is saved to from the pause screen
+pub const SAVE_PATH: &str = "savegame.json";
+
+/// The persistent part of a running game. Roto scripts, stats and textures are not stored,
+/// they get reloaded from the script when the save is loaded.
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub player: Player,
+    pub enemies: Vec<Enemy>,
+    pub projectiles: Vec<Projectile>,
+    pub pending_spawns: Vec<PendingSpawn>,
+    pub wave: u32,
+    pub next_entity_id: EntityId,
+    pub kills_by_weapon: HashMap<WeaponType, u32>,
+    #[serde(default)]
+    pub run_time: f32,
+    #[serde(default)]
+    pub seed: u64, // So a retry after loading replays the same run
+    #[serde(default)]
+    pub shard_bursts: Vec<ShardBurst>, // Saving during a crack must not cancel the shards
+    #[serde(default)]
+    pub pickups: Vec<Pickup>,
+    #[serde(default)]
+    pub barriers: Vec<Barrier>,
+}
@@ -0,0 +1,76 @@
+//! Serde support for the macroquad types that don't implement it, used by the save system.
+//! Vectors are stored as `[x, y]`.
+
+use macroquad::prelude::{Color, Vec2};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::visual_config::ColorConfig;
+
+/// Serde helpers for `Vec2` fields, use with `#[serde(with = "crate::serde_compat::vec2")]`
+pub mod vec2 {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(v: &Vec2, serializer: S) -> Result<S::Ok, S::Error> {
+        [v.x, v.y].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec2, D::Error> {
+        let [x, y] = <[f32; 2]>::deserialize(deserializer)?;
+        Ok(Vec2::new(x, y))
+    }
+}
+
+impl From<Color> for ColorConfig {
+    fn from(c: Color) -> Self {
+        Self::new(c.r, c.g, c.b, c.a)
+    }
+}
+
+impl From<ColorConfig> for Color {
+    fn from(c: ColorConfig) -> Self {
+        c.to_color()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Persisted {
+        #[serde(with = "vec2")]
+        pos: Vec2,
+    }
+
+    #[test]
+    fn test_positions_round_trip() {
+        let persisted = Persisted {
+            pos: Vec2::new(12.5, -3.0),
+        };
+
+        let json = serde_json::to_string(&persisted).unwrap();
+        assert_eq!(json, r#"{"pos":[12.5,-3.0]}"#);
+        let loaded: Persisted = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, persisted);
+    }
+
+    #[test]
+    fn test_color_config_round_trip() {
+        let config = ColorConfig::new(0.25, 0.5, 0.75, 1.0);
+        let json = serde_json::to_string(&config).unwrap();
+        let loaded: ColorConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(Color::from(loaded), config.to_color());
+
+        // Converting through a macroquad color keeps every component
+        let back = ColorConfig::from(Color::from(config));
+        assert_eq!(
+            (back.r, back.g, back.b, back.a),
+            (config.r, config.g, config.b, config.a)
+        );
+    }
+
+    #[test]
+    fn test_malformed_vector_is_an_error() {
+        assert!(serde_json::from_str::<Persisted>(r#"{"pos":[1.0]}"#).is_err());
+    }
+}
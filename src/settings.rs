@@ -0,0 +1,43 @@
+/// File the user settings are stored in, next to the game
+pub const SETTINGS_PATH: &str = "settings.cfg";
+
+/// User settings that persist between runs, stored as `key=value` lines
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Settings {
+    pub fullscreen: bool,
+    pub reduced_motion: bool,    // No flicker, flashes or pulsing effects
+    pub static_background: bool, // Keep the first wave background instead of shifting per wave
+}
+
+impl Settings {
+    /// Loads the settings, missing files or unknown keys fall back to the defaults
+    pub fn load() -> Self {
+        let mut settings = Self::default();
+        let Ok(content) = std::fs::read_to_string(SETTINGS_PATH) else {
+            return settings;
+        };
+
+        for line in content.lines() {
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim() == "true";
+                match key.trim() {
+                    "fullscreen" => settings.fullscreen = value,
+                    "reduced_motion" => settings.reduced_motion = value,
+                    "static_background" => settings.static_background = value,
+                    _ => {}
+                }
+            }
+        }
+        settings
+    }
+
+    pub fn save(&self) {
+        let content = format!(
+            "fullscreen={}\nreduced_motion={}\nstatic_background={}\n",
+            self.fullscreen, self.reduced_motion, self.static_background
+        );
+        if let Err(err) = std::fs::write(SETTINGS_PATH, content) {
+            eprintln!("ERROR saving {}: {}", SETTINGS_PATH, err);
+        }
+    }
+}
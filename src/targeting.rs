@@ -0,0 +1,95 @@
+use macroquad::prelude::*;
+
+use crate::barrier::{Barrier, has_line_of_sight};
+use crate::enemy::Enemy;
+
+/// Orders enemies by distance to `pos`, equally distant enemies by their id so the choice is stable
+fn compare_distance(pos: Vec2, a: &Enemy, b: &Enemy) -> std::cmp::Ordering {
+    let dist_a = (a.pos - pos).length_squared();
+    let dist_b = (b.pos - pos).length_squared();
+    dist_a.total_cmp(&dist_b).then(a.id.cmp(&b.id))
+}
+
+/// The enemy closest to `pos`, if there is any
+pub fn nearest_enemy(pos: Vec2, enemies: &[Enemy]) -> Option<&Enemy> {
+    k_nearest(pos, 1, enemies).first().copied()
+}
+
+/// Up to `k` enemies closest to `pos`, nearest first
+pub fn k_nearest(pos: Vec2, k: usize, enemies: &[Enemy]) -> Vec<&Enemy> {
+    let mut nearest: Vec<&Enemy> = enemies.iter().collect();
+    // Only the k nearest get sorted, the rest is cut off unordered
+    if k < nearest.len() {
+        nearest.select_nth_unstable_by(k, |a, b| compare_distance(pos, a, b));
+        nearest.truncate(k);
+    }
+    nearest.sort_by(|a, b| compare_distance(pos, a, b));
+    nearest
+}
+
+/// The enemy closest to `pos` whose center is within `radius` and that no barrier hides,
+/// if there is any
+pub fn nearest_visible_enemy_within<'a>(
+    pos: Vec2,
+    radius: f32,
+    enemies: &'a [Enemy],
+    barriers: &[Barrier],
+) -> Option<&'a Enemy> {
+    enemies
+        .iter()
+        .filter(|enemy| (enemy.pos - pos).length_squared() <= radius * radius)
+        .filter(|enemy| has_line_of_sight(pos, enemy.pos, barriers))
+        .min_by(|a, b| compare_distance(pos, a, b))
+}
+
+/// Number of enemies whose center is within `radius` of `pos`
+pub fn count_within(pos: Vec2, radius: f32, enemies: &[Enemy]) -> usize {
+    enemies
+        .iter()
+        .filter(|enemy| (enemy.pos - pos).length_squared() <= radius * radius)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::EntityId;
+
+    fn enemy_at(id: EntityId, x: f32, y: f32) -> Enemy {
+        Enemy::for_test(id, Vec2::new(x, y))
+    }
+
+    #[test]
+    fn test_nearest_enemy_empty() {
+        assert!(nearest_enemy(Vec2::ZERO, &[]).is_none());
+        assert!(k_nearest(Vec2::ZERO, 3, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_k_nearest_returns_all_when_k_exceeds_the_enemies() {
+        let enemies = vec![enemy_at(1, 30.0, 0.0), enemy_at(2, 10.0, 0.0)];
+        let ids: Vec<EntityId> = k_nearest(Vec2::ZERO, 10, &enemies)
+            .iter()
+            .map(|enemy| enemy.id)
+            .collect();
+        assert_eq!(ids, vec![2, 1]);
+        assert!(k_nearest(Vec2::ZERO, 0, &enemies).is_empty());
+    }
+
+    #[test]
+    fn test_nearest_enemy_tie_breaks_by_id() {
+        // Same distance to the origin, listed with the higher id first
+        let enemies = vec![
+            enemy_at(7, 0.0, 10.0),
+            enemy_at(3, 10.0, 0.0),
+            enemy_at(5, 50.0, 0.0),
+        ];
+        assert_eq!(nearest_enemy(Vec2::ZERO, &enemies).unwrap().id, 3);
+
+        let ids: Vec<EntityId> = k_nearest(Vec2::ZERO, 2, &enemies)
+            .iter()
+            .map(|enemy| enemy.id)
+            .collect();
+        assert_eq!(ids, vec![3, 7]);
+    }
+}
@@ -1,9 +1,10 @@
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::projectile::ProjectileType;
 
 /// RGB color configuration that can be used with Roto
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct ColorConfig {
     pub r: f32,
     pub g: f32,
@@ -55,7 +56,7 @@ impl ColorConfig {
 }
 
 /// Visual configuration for player
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct PlayerVisualConfig {
     pub circle_color: ColorConfig,
     pub indicator_color: ColorConfig,
@@ -73,11 +74,14 @@ impl PlayerVisualConfig {
 }
 
 /// Visual configuration for enemies
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct EnemyVisualConfig {
     pub circle_color: ColorConfig,
     pub indicator_color: ColorConfig,
-    pub indicator_size: f32, // Size multiplier for direction triangle
+    pub indicator_size: f32,    // Size multiplier for direction triangle
+    pub health_bar_width: f32,  // Width of the health bar shown when damaged
+    pub health_bar_height: f32, // Height of the health bar
+    pub health_bar_offset: f32, // Gap between the enemy circle and the health bar
 }
 
 impl EnemyVisualConfig {
@@ -86,6 +90,75 @@ impl EnemyVisualConfig {
             circle_color: ColorConfig::red(),
             indicator_color: ColorConfig::white(),
             indicator_size: 3.0,
+            health_bar_width: 30.0,
+            health_bar_height: 4.0,
+            health_bar_offset: 6.0,
+        }
+    }
+
+    pub fn teleporter_default() -> Self {
+        Self {
+            circle_color: ColorConfig::new(0.6, 0.3, 1.0, 1.0),
+            indicator_color: ColorConfig::white(),
+            indicator_size: 3.0,
+            health_bar_width: 30.0,
+            health_bar_height: 4.0,
+            health_bar_offset: 6.0,
+        }
+    }
+
+    pub fn berserker_default() -> Self {
+        Self {
+            circle_color: ColorConfig::new(1.0, 0.1, 0.4, 1.0),
+            indicator_color: ColorConfig::yellow(),
+            indicator_size: 3.5,
+            health_bar_width: 30.0,
+            health_bar_height: 4.0,
+            health_bar_offset: 6.0,
+        }
+    }
+
+    pub fn phaser_default() -> Self {
+        Self {
+            circle_color: ColorConfig::new(0.3, 0.9, 0.9, 1.0),
+            indicator_color: ColorConfig::white(),
+            indicator_size: 3.0,
+            health_bar_width: 30.0,
+            health_bar_height: 4.0,
+            health_bar_offset: 6.0,
+        }
+    }
+
+    pub fn builder_default() -> Self {
+        Self {
+            circle_color: ColorConfig::new(0.65, 0.45, 0.25, 1.0),
+            indicator_color: ColorConfig::white(),
+            indicator_size: 3.0,
+            health_bar_width: 30.0,
+            health_bar_height: 4.0,
+            health_bar_offset: 6.0,
+        }
+    }
+
+    pub fn glass_default() -> Self {
+        Self {
+            circle_color: ColorConfig::new(0.7, 0.9, 1.0, 0.6),
+            indicator_color: ColorConfig::white(),
+            indicator_size: 3.0,
+            health_bar_width: 30.0,
+            health_bar_height: 4.0,
+            health_bar_offset: 6.0,
+        }
+    }
+
+    pub fn dummy_default() -> Self {
+        Self {
+            circle_color: ColorConfig::new(0.55, 0.5, 0.45, 1.0),
+            indicator_color: ColorConfig::white(),
+            indicator_size: 3.0,
+            health_bar_width: 40.0,
+            health_bar_height: 4.0,
+            health_bar_offset: 6.0,
         }
     }
 
@@ -94,12 +167,15 @@ impl EnemyVisualConfig {
             circle_color: ColorConfig::orange(),
             indicator_color: ColorConfig::white(),
             indicator_size: 3.0,
+            health_bar_width: 30.0,
+            health_bar_height: 4.0,
+            health_bar_offset: 6.0,
         }
     }
 }
 
 /// Visual configuration for projectiles
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct ProjectileVisualConfig {
     pub primary_color: ColorConfig,
     pub secondary_color: ColorConfig, // For blending/effects
@@ -115,7 +191,7 @@ impl From<ProjectileType> for ProjectileVisualConfig {
                 indicator_color: ColorConfig::white(),
             },
             ProjectileType::Pulse => Self {
-                primary_color: ColorConfig::new(0.5, 0.0, 0.5, 0.3), // Semi-transparent purple
+                primary_color: ColorConfig::new(0.5, 0.0, 0.5, 0.3), // Unused, pulses fill with pulse_blend
                 secondary_color: ColorConfig::purple(),              // Outline color
                 indicator_color: ColorConfig::white(),
             },
@@ -124,12 +200,17 @@ impl From<ProjectileType> for ProjectileVisualConfig {
                 secondary_color: ColorConfig::yellow(), // For direction triangle
                 indicator_color: ColorConfig::yellow(),
             },
+            ProjectileType::GravityWell => Self {
+                primary_color: ColorConfig::new(0.5, 0.3, 1.0, 0.8), // Swirl
+                secondary_color: ColorConfig::new(0.5, 0.3, 1.0, 0.4), // Outline of the reach
+                indicator_color: ColorConfig::white(),               // Center
+            },
         }
     }
 }
 
 /// Blend configuration for effects like pulse
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct BlendConfig {
     pub inner_color: ColorConfig,
     pub outer_color: ColorConfig,
@@ -160,6 +241,13 @@ impl BlendConfig {
             ColorConfig::new(0.3, 0.0, 0.3, 0.1), // Dark purple edge
         )
     }
+
+    pub fn background_default() -> Self {
+        Self::new(
+            ColorConfig::black(),                   // First wave
+            ColorConfig::new(0.18, 0.0, 0.06, 1.0), // Final wave, a dark red
+        )
+    }
 }
 
 /// Complete visual configuration for the game
@@ -168,10 +256,17 @@ pub struct GameVisualConfig {
     pub player: PlayerVisualConfig,
     pub basic_enemy: EnemyVisualConfig,
     pub chaser_enemy: EnemyVisualConfig,
+    pub teleporter_enemy: EnemyVisualConfig,
+    pub berserker_enemy: EnemyVisualConfig,
+    pub phaser_enemy: EnemyVisualConfig,
+    pub builder_enemy: EnemyVisualConfig,
+    pub glass_enemy: EnemyVisualConfig, // Also colors the shards it bursts into
     pub energy_ball: ProjectileVisualConfig,
     pub pulse: ProjectileVisualConfig,
     pub homing_missile: ProjectileVisualConfig,
+    pub gravity_well: ProjectileVisualConfig,
     pub pulse_blend: BlendConfig,
+    pub background: BlendConfig, // Background color from the first to the final wave
 }
 
 #[derive(Debug, Clone, Default)]
@@ -185,10 +280,17 @@ impl GameVisualConfig {
             player: PlayerVisualConfig::default(),
             basic_enemy: EnemyVisualConfig::basic_default(),
             chaser_enemy: EnemyVisualConfig::chaser_default(),
+            teleporter_enemy: EnemyVisualConfig::teleporter_default(),
+            berserker_enemy: EnemyVisualConfig::berserker_default(),
+            phaser_enemy: EnemyVisualConfig::phaser_default(),
+            builder_enemy: EnemyVisualConfig::builder_default(),
+            glass_enemy: EnemyVisualConfig::glass_default(),
             energy_ball: ProjectileVisualConfig::from(ProjectileType::EnergyBall),
             pulse: ProjectileVisualConfig::from(ProjectileType::Pulse),
             homing_missile: ProjectileVisualConfig::from(ProjectileType::HomingMissile),
+            gravity_well: ProjectileVisualConfig::from(ProjectileType::GravityWell),
             pulse_blend: BlendConfig::pulse_default(),
+            background: BlendConfig::background_default(),
         }
     }
 }
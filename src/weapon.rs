@@ -1,54 +1,377 @@
 use macroquad::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::entity::SpawnCommand;
+use crate::barrier::{Barrier, has_line_of_sight};
+use crate::enemy::Enemy;
+use crate::entity::{EntityId, SpawnCommand, find_by_id};
 use crate::projectile::{ProjectileStats, ProjectileType};
+use crate::targeting::nearest_visible_enemy_within;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WeaponType {
     EnergyBall,
     Pulse,
     HomingMissile,
+    Cannon,
+    GravityWell,
+    Adaptive,
+    Tether,
+}
+
+impl WeaponType {
+    /// Weapons that fire where the player aims with the mouse
+    pub fn is_aimed(&self) -> bool {
+        matches!(
+            self,
+            WeaponType::EnergyBall
+                | WeaponType::Cannon
+                | WeaponType::GravityWell
+                | WeaponType::Adaptive
+        )
+    }
+}
+
+/// Distance in front of the player at which a gravity well is placed
+const GRAVITY_WELL_DISTANCE: f32 = 150.0;
+
+/// Damage multiplier of each shot in the adaptive ring compared to the single shot
+const ADAPTIVE_SPREAD_DAMAGE: f32 = 0.4;
+/// Radius around the player in which the adaptive weapon counts enemies
+const DEFAULT_ADAPTIVE_RADIUS: f32 = 200.0;
+/// Number of nearby enemies from which on the adaptive weapon fires a ring
+const DEFAULT_ADAPTIVE_THRESHOLD: u32 = 4;
+
+/// Maximum number of weapons the player can carry at once
+pub const MAX_WEAPON_SLOTS: usize = 3;
+
+/// Heat gauge size of an overheating weapon
+const DEFAULT_MAX_HEAT: f32 = 100.0;
+/// Heat added by every shot of an overheating weapon
+const DEFAULT_HEAT_PER_SHOT: f32 = 20.0;
+/// Heat an overheating weapon loses per second
+const DEFAULT_HEAT_COOLDOWN_RATE: f32 = 40.0;
+
+/// How a weapon limits its fire rate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum FireMode {
+    #[default]
+    Cooldown, // Default: wait `cooldown` seconds after every shot
+    Overheat, // Fire freely until the heat gauge is full, then cool down completely
+}
+
+/// Overheat parameters a script can give a weapon
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct OverheatConfig {
+    pub fire_mode: FireMode,
+    pub max_heat: f32,
+    pub heat_per_shot: f32,
+    pub cooldown_rate: f32, // Heat lost per second
+}
+
+impl Default for OverheatConfig {
+    fn default() -> Self {
+        Self {
+            fire_mode: FireMode::Cooldown,
+            max_heat: DEFAULT_MAX_HEAT,
+            heat_per_shot: DEFAULT_HEAT_PER_SHOT,
+            cooldown_rate: DEFAULT_HEAT_COOLDOWN_RATE,
+        }
+    }
+}
+
+/// What happens to the in-flight projectiles of a weapon that gets replaced
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReplacedProjectiles {
+    Keep,  // Default: projectiles finish their lifetime, they don't depend on the weapon
+    Clear, // Projectiles of the replaced weapon despawn immediately
+}
+
+/// Charge-up configuration for manually fired weapons like the Cannon
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ChargeConfig {
+    pub max_charge_time: f32, // Seconds until the shot is fully charged
+    pub damage_scale: f32,    // Damage multiplier at full charge
+    pub size_scale: f32,      // Radius multiplier at full charge
+}
+
+impl Default for ChargeConfig {
+    fn default() -> Self {
+        Self {
+            max_charge_time: 2.0,
+            damage_scale: 4.0,
+            size_scale: 2.5,
+        }
+    }
+}
+
+/// Reach and strength of the gravity well
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GravityWellConfig {
+    pub radius: f32,
+    pub pull_strength: f32, // Speed per second added towards the center
 }
 
+impl Default for GravityWellConfig {
+    fn default() -> Self {
+        let stats = ProjectileStats::from(ProjectileType::GravityWell);
+        Self {
+            radius: stats.radius,
+            pull_strength: stats.pull_strength,
+        }
+    }
+}
+
+/// When the adaptive weapon switches from a single shot to a ring
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AdaptiveConfig {
+    pub radius: f32,    // Enemies closer than this to the player count as nearby
+    pub threshold: u32, // Nearby enemies needed for the ring
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        Self {
+            radius: DEFAULT_ADAPTIVE_RADIUS,
+            threshold: DEFAULT_ADAPTIVE_THRESHOLD,
+        }
+    }
+}
+
+/// Reach, damage and slow of the tether beam
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TetherConfig {
+    pub range: f32,       // The beam breaks when the target gets farther away than this
+    pub dps: f32,         // Damage per second to the tethered enemy
+    pub slow_factor: f32, // Multiplier on the tethered enemy's max speed, 1.0 means no slow
+}
+
+impl Default for TetherConfig {
+    fn default() -> Self {
+        Self {
+            range: 250.0,
+            dps: 12.0,
+            slow_factor: 0.7,
+        }
+    }
+}
+
+/// Permanent bonus a weapon earns for its kills within a run, on top of level-ups
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct MasteryConfig {
+    pub kills_per_tier: u32, // Kills needed for each mastery tier, 0 turns mastery off
+    pub damage_per_tier: f32,
+}
+
+impl Default for MasteryConfig {
+    fn default() -> Self {
+        Self {
+            kills_per_tier: 25,
+            damage_per_tier: 1.0,
+        }
+    }
+}
+
+/// Slow debuff applied to enemies touched by a slow-field pulse
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SlowConfig {
+    pub factor: f32,   // Multiplier on the enemy max speed, 1.0 means no slow
+    pub duration: f32, // Seconds the slow lasts after the last contact
+}
+
+impl Default for SlowConfig {
+    fn default() -> Self {
+        Self {
+            factor: 1.0,
+            duration: 0.0,
+        }
+    }
+}
+
+/// Script-tunable weapon parameters applied on top of the per-type defaults
 #[derive(Debug, Clone, Copy)]
+pub struct WeaponTuning {
+    pub charge: ChargeConfig,
+    pub pulse_expands: bool,
+    pub pulse_slow: SlowConfig,
+    pub lifesteal: f32,
+    pub energy_ball_full_circle: bool, // Whether the level 5 EnergyBall upgrade fires a 360° ring
+    pub pulse_falloff: f32,
+    pub energy_ball_overheat: OverheatConfig,
+    pub gravity_well: GravityWellConfig,
+    pub adaptive: AdaptiveConfig,
+    pub mastery: MasteryConfig,
+    pub tether: TetherConfig,
+}
+
+impl Default for WeaponTuning {
+    fn default() -> Self {
+        Self {
+            charge: ChargeConfig::default(),
+            pulse_expands: false,
+            pulse_slow: SlowConfig::default(),
+            lifesteal: 0.0,
+            energy_ball_full_circle: true,
+            pulse_falloff: 0.0,
+            energy_ball_overheat: OverheatConfig::default(),
+            gravity_well: GravityWellConfig::default(),
+            adaptive: AdaptiveConfig::default(),
+            mastery: MasteryConfig::default(),
+            tether: TetherConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct WeaponStats {
     pub cooldown: f32,
     pub projectile_count: u32,
     pub spread_angle: f32, // In degrees, for multiple projectiles
     pub projectile_stats: ProjectileStats,
+    pub charge: ChargeConfig, // Only used by charged weapons (Cannon)
+    pub lifesteal: f32,       // Fraction of dealt damage that heals the player
+    #[serde(default)]
+    pub full_circle: bool, // Spread projectile_count evenly over 360° instead of around the facing
+    #[serde(default)]
+    pub fire_mode: FireMode,
+    #[serde(default)]
+    pub heat: f32, // Current heat, only used in `FireMode::Overheat`
+    #[serde(default = "default_max_heat")]
+    pub max_heat: f32,
+    #[serde(default = "default_heat_per_shot")]
+    pub heat_per_shot: f32,
+    #[serde(default = "default_heat_cooldown_rate")]
+    pub cooldown_rate: f32, // Heat lost per second
+    #[serde(default = "default_adaptive_radius")]
+    pub adaptive_radius: f32, // Only used by the adaptive weapon
+    #[serde(default = "default_adaptive_threshold")]
+    pub adaptive_threshold: u32,
+    #[serde(default)]
+    pub mastery: MasteryConfig,
+    #[serde(default)]
+    pub tether: TetherConfig, // Only used by the tether
+}
+
+// Saves from before the overheat mode don't have its fields
+fn default_max_heat() -> f32 {
+    DEFAULT_MAX_HEAT
+}
+
+fn default_heat_per_shot() -> f32 {
+    DEFAULT_HEAT_PER_SHOT
+}
+
+fn default_heat_cooldown_rate() -> f32 {
+    DEFAULT_HEAT_COOLDOWN_RATE
+}
+
+// Saves from before the adaptive weapon don't have its fields
+fn default_adaptive_radius() -> f32 {
+    DEFAULT_ADAPTIVE_RADIUS
+}
+
+fn default_adaptive_threshold() -> u32 {
+    DEFAULT_ADAPTIVE_THRESHOLD
 }
 
 impl From<WeaponType> for WeaponStats {
     fn from(weapon_type: WeaponType) -> Self {
+        // Only the fire rate and the projectiles differ between the weapons
+        let base = Self {
+            cooldown: 1.0,
+            projectile_count: 1,
+            spread_angle: 0.0,
+            projectile_stats: ProjectileStats::from(ProjectileType::EnergyBall),
+            charge: ChargeConfig::default(),
+            lifesteal: 0.0,
+            full_circle: false,
+            fire_mode: FireMode::Cooldown,
+            heat: 0.0,
+            max_heat: DEFAULT_MAX_HEAT,
+            heat_per_shot: DEFAULT_HEAT_PER_SHOT,
+            cooldown_rate: DEFAULT_HEAT_COOLDOWN_RATE,
+            adaptive_radius: DEFAULT_ADAPTIVE_RADIUS,
+            adaptive_threshold: DEFAULT_ADAPTIVE_THRESHOLD,
+            mastery: MasteryConfig::default(),
+            tether: TetherConfig::default(),
+        };
         match weapon_type {
             WeaponType::EnergyBall => Self {
                 cooldown: 1.5, // Fire every 1.5 seconds
                 projectile_count: 1,
                 spread_angle: 0.0,
                 projectile_stats: ProjectileStats::from(ProjectileType::EnergyBall),
+                ..base
             },
             WeaponType::Pulse => Self {
                 cooldown: 3.0, // Fire every 3 seconds
                 projectile_count: 1,
                 spread_angle: 0.0, // Not used for pulse
                 projectile_stats: ProjectileStats::from(ProjectileType::Pulse),
+                ..base
             },
             WeaponType::HomingMissile => Self {
                 cooldown: 2.0, // Fire every 2 seconds
                 projectile_count: 1,
                 spread_angle: 0.0, // Not used for single homing missile
                 projectile_stats: ProjectileStats::from(ProjectileType::HomingMissile),
+                ..base
+            },
+            WeaponType::Cannon => Self {
+                cooldown: 0.5, // Recovery time after releasing a shot
+                projectile_count: 1,
+                spread_angle: 0.0, // Not used for the cannon
+                projectile_stats: ProjectileStats {
+                    damage: 25.0,
+                    speed: 400.0,
+                    radius: 10.0,
+                    ..ProjectileStats::from(ProjectileType::EnergyBall)
+                },
+                ..base
+            },
+            WeaponType::GravityWell => Self {
+                cooldown: 5.0, // Place a well every 5 seconds
+                projectile_count: 1,
+                spread_angle: 0.0, // Not used for the gravity well
+                projectile_stats: ProjectileStats::from(ProjectileType::GravityWell),
+                ..base
+            },
+            WeaponType::Adaptive => Self {
+                cooldown: 1.2,
+                projectile_count: 8, // Size of the ring when surrounded
+                spread_angle: 0.0,   // Not used, the ring is always a full circle
+                projectile_stats: ProjectileStats {
+                    damage: 15.0,
+                    ..ProjectileStats::from(ProjectileType::EnergyBall)
+                },
+                full_circle: true,
+                ..base
+            },
+            WeaponType::Tether => Self {
+                cooldown: 0.0, // The beam is always on
+                projectile_count: 0,
+                spread_angle: 0.0, // Not used for the tether
+                projectile_stats: ProjectileStats::from(ProjectileType::EnergyBall), // Not used
+                ..base
             },
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Weapon {
     pub weapon_type: WeaponType,
     pub level: u32, // For future use with Roto integration
     pub cooldown_remaining: f32,
+    pub charge_time: f32, // Time the charge key has been held, for charged weapons
+    #[serde(default)]
+    pub overheated: bool, // Locked out until the heat is back to zero
     pub stats: WeaponStats,
+    #[serde(default)]
+    pub kills: u32, // Kills this run, they raise the mastery tier
+    #[serde(default)]
+    pub mastery_tier: u32,
+    #[serde(default)]
+    pub tether_target: Option<EntityId>, // Enemy the tether is linked to
 }
 
 impl Weapon {
@@ -59,116 +382,364 @@ impl Weapon {
             weapon_type,
             level: 1,                // Start at level 1
             cooldown_remaining: 0.0, // Start ready to fire
+            charge_time: 0.0,
+            overheated: false,
             stats,
+            kills: 0,
+            mastery_tier: 0,
+            tether_target: None,
+        }
+    }
+
+    pub fn apply_tuning(&mut self, tuning: &WeaponTuning) {
+        self.stats.charge = tuning.charge;
+        self.stats.lifesteal = tuning.lifesteal;
+        self.stats.mastery = tuning.mastery;
+        if self.weapon_type == WeaponType::EnergyBall {
+            // The ring comes with the level 5 breakpoint
+            self.stats.full_circle = tuning.energy_ball_full_circle && self.level >= 5;
+            self.apply_overheat(&tuning.energy_ball_overheat);
+        }
+        if self.weapon_type == WeaponType::Pulse {
+            self.stats.projectile_stats.pulse_expands = tuning.pulse_expands;
+            self.stats.projectile_stats.slow = tuning.pulse_slow;
+            self.stats.projectile_stats.falloff = tuning.pulse_falloff;
+        }
+        if self.weapon_type == WeaponType::GravityWell {
+            self.stats.projectile_stats.radius = tuning.gravity_well.radius;
+            self.stats.projectile_stats.pull_strength = tuning.gravity_well.pull_strength;
+        }
+        if self.weapon_type == WeaponType::Adaptive {
+            self.stats.adaptive_radius = tuning.adaptive.radius;
+            self.stats.adaptive_threshold = tuning.adaptive.threshold;
+        }
+        if self.weapon_type == WeaponType::Tether {
+            self.stats.tether = tuning.tether;
+        }
+    }
+
+    /// Applies changed tuning to a weapon that may be upgraded already: the tuning replaces
+    /// the base stats and the level ups and mastery tiers are applied on top again
+    pub fn retune(&mut self, tuning: &WeaponTuning) {
+        let mut retuned = Weapon::new(self.weapon_type);
+        retuned.apply_tuning(tuning);
+        for _ in 1..self.level {
+            retuned.level_up(tuning);
+        }
+        for _ in 0..self.mastery_tier {
+            retuned.apply_mastery_tier();
         }
+        let heat = self.stats.heat;
+        self.stats = retuned.stats;
+        self.stats.heat = heat;
+    }
+
+    fn apply_overheat(&mut self, overheat: &OverheatConfig) {
+        self.stats.fire_mode = overheat.fire_mode;
+        self.stats.max_heat = overheat.max_heat;
+        self.stats.heat_per_shot = overheat.heat_per_shot;
+        self.stats.cooldown_rate = overheat.cooldown_rate;
     }
 
     pub fn update(&mut self, dt: f32) {
-        if self.cooldown_remaining > 0.0 {
-            self.cooldown_remaining -= dt;
+        match self.stats.fire_mode {
+            FireMode::Cooldown => {
+                if self.cooldown_remaining > 0.0 {
+                    self.cooldown_remaining -= dt;
+                }
+            }
+            FireMode::Overheat => {
+                self.stats.heat = (self.stats.heat - self.stats.cooldown_rate * dt).max(0.0);
+                if self.stats.heat <= 0.0 {
+                    self.overheated = false;
+                }
+            }
+        }
+    }
+
+    /// Fill of the heat gauge between 0.0 (cold) and 1.0 (overheated)
+    pub fn heat_fraction(&self) -> f32 {
+        if self.stats.max_heat > 0.0 {
+            (self.stats.heat / self.stats.max_heat).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// How far the cooldown has recovered, 1.0 means ready to fire
+    pub fn readiness(&self) -> f32 {
+        if self.stats.cooldown > 0.0 {
+            1.0 - (self.cooldown_remaining / self.stats.cooldown).clamp(0.0, 1.0)
+        } else {
+            1.0
         }
     }
 
+    /// Health the player gets back for dealing `damage` with this weapon
+    pub fn lifesteal_heal(&self, damage: f32) -> f32 {
+        damage * self.stats.lifesteal
+    }
+
+    /// Counts a kill, returns true when it reached a new mastery tier and applied its bonus
+    pub fn add_kill(&mut self) -> bool {
+        self.kills += 1;
+        let kills_per_tier = self.stats.mastery.kills_per_tier;
+        if kills_per_tier == 0 || self.kills / kills_per_tier <= self.mastery_tier {
+            return false;
+        }
+        self.mastery_tier += 1;
+        self.apply_mastery_tier();
+        true
+    }
+
+    fn apply_mastery_tier(&mut self) {
+        if self.weapon_type == WeaponType::Tether {
+            self.stats.tether.dps += self.stats.mastery.damage_per_tier;
+        } else {
+            self.stats.projectile_stats.damage += self.stats.mastery.damage_per_tier;
+        }
+    }
+
+    /// Keeps the tethered enemy while it exists, is in range and not behind a barrier, otherwise
+    /// links the nearest such enemy. Returns the enemy the tether is linked to now.
+    pub fn update_tether_target(
+        &mut self,
+        player_pos: Vec2,
+        enemies: &[Enemy],
+        barriers: &[Barrier],
+    ) -> Option<EntityId> {
+        let range = self.stats.tether.range;
+        let current = self
+            .tether_target
+            .and_then(|id| find_by_id(enemies, id))
+            .filter(|enemy| enemy.pos.distance_squared(player_pos) <= range * range)
+            .filter(|enemy| has_line_of_sight(player_pos, enemy.pos, barriers));
+        self.tether_target = current
+            .or_else(|| nearest_visible_enemy_within(player_pos, range, enemies, barriers))
+            .map(|enemy| enemy.id);
+        self.tether_target
+    }
+
     pub fn can_fire(&self) -> bool {
-        self.cooldown_remaining <= 0.0
+        match self.stats.fire_mode {
+            FireMode::Cooldown => self.cooldown_remaining <= 0.0,
+            FireMode::Overheat => !self.overheated,
+        }
     }
 
-    pub fn fire(&mut self, player_pos: Vec2, player_facing: Vec2) -> Vec<SpawnCommand> {
+    /// Fires if the cooldown or heat allows, the spawn commands are appended to `commands`.
+    /// `nearby_enemies` is the number of enemies within `adaptive_radius` of the player.
+    pub fn fire(
+        &mut self,
+        player_pos: Vec2,
+        player_facing: Vec2,
+        nearby_enemies: usize,
+        commands: &mut Vec<SpawnCommand>,
+    ) {
         if !self.can_fire() {
-            return Vec::new();
+            return;
         }
 
-        // Reset cooldown
-        self.cooldown_remaining = self.stats.cooldown;
+        match self.stats.fire_mode {
+            FireMode::Cooldown => self.cooldown_remaining = self.stats.cooldown,
+            FireMode::Overheat => {
+                self.stats.heat += self.stats.heat_per_shot;
+                if self.stats.heat >= self.stats.max_heat {
+                    self.stats.heat = self.stats.max_heat;
+                    self.overheated = true;
+                }
+            }
+        }
 
         match self.weapon_type {
-            WeaponType::EnergyBall => self.fire_energy_ball(player_pos, player_facing),
-            WeaponType::Pulse => self.fire_pulse(player_pos),
-            WeaponType::HomingMissile => self.fire_homing_missile(player_pos, player_facing),
+            WeaponType::EnergyBall => self.fire_spread(
+                ProjectileType::EnergyBall,
+                player_pos,
+                player_facing,
+                commands,
+            ),
+            WeaponType::Pulse => self.fire_pulse(player_pos, commands),
+            WeaponType::HomingMissile => self.fire_spread(
+                ProjectileType::HomingMissile,
+                player_pos,
+                player_facing,
+                commands,
+            ),
+            WeaponType::Cannon => self.fire_charged(player_pos, player_facing, commands),
+            WeaponType::GravityWell => self.fire_gravity_well(player_pos, player_facing, commands),
+            WeaponType::Adaptive => {
+                self.fire_adaptive(player_pos, player_facing, nearby_enemies, commands)
+            }
+            WeaponType::Tether => {
+                // Nothing to spawn, the game state damages the tethered enemy every tick
+            }
         }
     }
 
-    fn fire_energy_ball(&self, player_pos: Vec2, player_facing: Vec2) -> Vec<SpawnCommand> {
-        let mut commands = Vec::new();
+    /// Charges while `holding` is true and fires the charged shot on release
+    pub fn charge(
+        &mut self,
+        dt: f32,
+        holding: bool,
+        player_pos: Vec2,
+        player_facing: Vec2,
+        commands: &mut Vec<SpawnCommand>,
+    ) {
+        if holding {
+            if self.can_fire() {
+                self.charge_time = (self.charge_time + dt).min(self.stats.charge.max_charge_time);
+            }
+        } else if self.charge_time > 0.0 {
+            self.fire(player_pos, player_facing, 0, commands);
+        }
+    }
 
-        if self.stats.projectile_count == 1 {
-            // Single projectile in facing direction
-            let vel = player_facing.normalize() * self.stats.projectile_stats.speed;
+    /// Damage for the HUD and the cards, the tether beam has no projectiles and shows its
+    /// damage per second instead
+    pub fn damage_label(&self) -> String {
+        if self.weapon_type == WeaponType::Tether {
+            format!("{:.0}/s", self.stats.tether.dps)
+        } else {
+            format!("{:.0}", self.stats.projectile_stats.damage)
+        }
+    }
+
+    /// Current charge between 0.0 (empty) and 1.0 (fully charged)
+    pub fn charge_fraction(&self) -> f32 {
+        if self.stats.charge.max_charge_time > 0.0 {
+            (self.charge_time / self.stats.charge.max_charge_time).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    fn fire_charged(
+        &mut self,
+        player_pos: Vec2,
+        player_facing: Vec2,
+        commands: &mut Vec<SpawnCommand>,
+    ) {
+        let t = self.charge_fraction();
+        self.charge_time = 0.0;
+
+        // Scale damage and size linearly from 1x to the configured multiplier at full charge
+        let mut stats = self.stats.projectile_stats;
+        stats.damage *= 1.0 + (self.stats.charge.damage_scale - 1.0) * t;
+        stats.radius *= 1.0 + (self.stats.charge.size_scale - 1.0) * t;
+
+        let vel = player_facing.normalize() * stats.speed;
+        commands.push(SpawnCommand::Projectile {
+            projectile_type: ProjectileType::EnergyBall,
+            pos: player_pos,
+            vel,
+            stats,
+            weapon_type: self.weapon_type,
+            visual_config: None,
+        });
+    }
+
+    /// Energy balls and homing missiles: one shot in facing direction, a spread or a full ring
+    fn fire_spread(
+        &self,
+        projectile_type: ProjectileType,
+        player_pos: Vec2,
+        player_facing: Vec2,
+        commands: &mut Vec<SpawnCommand>,
+    ) {
+        let count = self.stats.projectile_count;
+        let mut push = |direction: Vec2| {
             commands.push(SpawnCommand::Projectile {
-                projectile_type: ProjectileType::EnergyBall,
+                projectile_type,
                 pos: player_pos,
-                vel,
+                vel: direction.normalize() * self.stats.projectile_stats.speed,
                 stats: self.stats.projectile_stats,
+                weapon_type: self.weapon_type,
+                visual_config: None,
             });
+        };
+
+        if count == 1 {
+            // Single projectile in facing direction
+            push(player_facing);
+        } else if self.stats.full_circle {
+            // Ring of projectiles evenly spaced around the player, starting at the facing
+            let angle_step = std::f32::consts::TAU / count as f32;
+            for i in 0..count {
+                push(self.rotate_vector(player_facing, i as f32 * angle_step));
+            }
         } else {
             // Multiple projectiles with spread
             let spread_rad = self.stats.spread_angle.to_radians();
-            let angle_step = if self.stats.projectile_count > 1 {
-                spread_rad * 2.0 / (self.stats.projectile_count - 1) as f32
+            let angle_step = if count > 1 {
+                spread_rad * 2.0 / (count - 1) as f32
             } else {
                 0.0
             };
 
-            for i in 0..self.stats.projectile_count {
+            for i in 0..count {
                 let angle_offset = -spread_rad + (i as f32) * angle_step;
-                let direction = self.rotate_vector(player_facing, angle_offset);
-                let vel = direction.normalize() * self.stats.projectile_stats.speed;
-
-                commands.push(SpawnCommand::Projectile {
-                    projectile_type: ProjectileType::EnergyBall,
-                    pos: player_pos,
-                    vel,
-                    stats: self.stats.projectile_stats,
-                });
+                push(self.rotate_vector(player_facing, angle_offset));
             }
         }
+    }
+
+    /// A strong single shot, or a weaker ring once enough enemies are close
+    fn fire_adaptive(
+        &self,
+        player_pos: Vec2,
+        player_facing: Vec2,
+        nearby_enemies: usize,
+        commands: &mut Vec<SpawnCommand>,
+    ) {
+        let surrounded = nearby_enemies >= self.stats.adaptive_threshold as usize;
+        let (count, damage_scale) = if surrounded {
+            (self.stats.projectile_count.max(1), ADAPTIVE_SPREAD_DAMAGE)
+        } else {
+            (1, 1.0)
+        };
+
+        let mut stats = self.stats.projectile_stats;
+        stats.damage *= damage_scale;
+        let angle_step = std::f32::consts::TAU / count as f32;
+        for i in 0..count {
+            let direction = self.rotate_vector(player_facing, i as f32 * angle_step);
+            commands.push(SpawnCommand::Projectile {
+                projectile_type: ProjectileType::EnergyBall,
+                pos: player_pos,
+                vel: direction.normalize() * stats.speed,
+                stats,
+                weapon_type: self.weapon_type,
+                visual_config: None,
+            });
+        }
+    }
 
-        commands
+    /// Places a stationary well in front of the player
+    fn fire_gravity_well(
+        &self,
+        player_pos: Vec2,
+        player_facing: Vec2,
+        commands: &mut Vec<SpawnCommand>,
+    ) {
+        commands.push(SpawnCommand::Projectile {
+            projectile_type: ProjectileType::GravityWell,
+            pos: player_pos + player_facing.normalize_or_zero() * GRAVITY_WELL_DISTANCE,
+            vel: Vec2::ZERO,
+            stats: self.stats.projectile_stats,
+            weapon_type: self.weapon_type,
+            visual_config: None,
+        });
     }
 
-    fn fire_pulse(&self, player_pos: Vec2) -> Vec<SpawnCommand> {
-        vec![SpawnCommand::Projectile {
+    fn fire_pulse(&self, player_pos: Vec2, commands: &mut Vec<SpawnCommand>) {
+        commands.push(SpawnCommand::Projectile {
             projectile_type: ProjectileType::Pulse,
             pos: player_pos,
             vel: Vec2::ZERO,
             stats: self.stats.projectile_stats,
-        }]
-    }
-
-    fn fire_homing_missile(&self, player_pos: Vec2, player_facing: Vec2) -> Vec<SpawnCommand> {
-        // For now, fire in facing direction. The homing behavior will take over during update
-        if self.stats.projectile_count == 1 {
-            let vel = player_facing.normalize() * self.stats.projectile_stats.speed;
-            vec![SpawnCommand::Projectile {
-                projectile_type: ProjectileType::HomingMissile,
-                pos: player_pos,
-                vel,
-                stats: self.stats.projectile_stats,
-            }]
-        } else {
-            let mut commands = Vec::new();
-            let spread_rad = self.stats.spread_angle.to_radians();
-            let angle_step = if self.stats.projectile_count > 1 {
-                spread_rad * 2.0 / (self.stats.projectile_count - 1) as f32
-            } else {
-                0.0
-            };
-
-            for i in 0..self.stats.projectile_count {
-                let angle_offset = -spread_rad + (i as f32) * angle_step;
-                let direction = self.rotate_vector(player_facing, angle_offset);
-                let vel = direction.normalize() * self.stats.projectile_stats.speed;
-
-                commands.push(SpawnCommand::Projectile {
-                    projectile_type: ProjectileType::HomingMissile,
-                    pos: player_pos,
-                    vel,
-                    stats: self.stats.projectile_stats,
-                });
-            }
-
-            commands
-        }
+            weapon_type: self.weapon_type,
+            visual_config: None,
+        });
     }
 
     fn rotate_vector(&self, vec: Vec2, angle_rad: f32) -> Vec2 {
@@ -178,7 +749,7 @@ impl Weapon {
     }
 
     // Level up the weapon, improving its stats
-    pub fn level_up(&mut self) {
+    pub fn level_up(&mut self, tuning: &WeaponTuning) {
         self.level += 1;
 
         // Improve weapon stats based on weapon type and level
@@ -187,6 +758,8 @@ impl Weapon {
                 if self.level >= 5 {
                     self.stats.projectile_count += 3;
                     self.stats.spread_angle = 75.0;
+                    // From the breakpoint on the shots form a full ring, unless the script disables it
+                    self.stats.full_circle = tuning.energy_ball_full_circle;
 
                     // Reduce cooldown by 5% per level (min 0.5s)
                     self.stats.cooldown = (self.stats.cooldown * 0.85).max(0.1);
@@ -245,6 +818,58 @@ impl Weapon {
                     self.stats.projectile_stats.speed *= 1.10;
                 }
             }
+            WeaponType::Cannon => {
+                if self.level >= 5 {
+                    // Charge faster (min 0.5s)
+                    self.stats.charge.max_charge_time =
+                        (self.stats.charge.max_charge_time * 0.8).max(0.5);
+                    self.stats.projectile_stats.damage += 10.0;
+                } else {
+                    // Increase damage by 5
+                    self.stats.projectile_stats.damage += 5.0;
+                    // Increase shot speed by 5%
+                    self.stats.projectile_stats.speed *= 1.05;
+                }
+            }
+            WeaponType::GravityWell => {
+                if self.level >= 5 {
+                    self.stats.cooldown = (self.stats.cooldown * 0.85).max(1.5);
+                    self.stats.projectile_stats.time_to_live += 0.5;
+                } else {
+                    // Reduce cooldown by 8% per level (min 2.0s)
+                    self.stats.cooldown = (self.stats.cooldown * 0.92).max(2.0);
+                    // The well lasts a bit longer
+                    self.stats.projectile_stats.time_to_live += 0.25;
+                }
+            }
+            WeaponType::Adaptive => {
+                if self.level >= 5 {
+                    // A denser ring that triggers earlier
+                    self.stats.projectile_count += 4;
+                    // Keep at least one enemy, a threshold of 0 would fire the ring all the time
+                    self.stats.adaptive_threshold =
+                        self.stats.adaptive_threshold.saturating_sub(1).max(1);
+                    self.stats.projectile_stats.damage += 4.0;
+                } else {
+                    // Reduce cooldown by 5% per level (min 0.5s)
+                    self.stats.cooldown = (self.stats.cooldown * 0.95).max(0.5);
+                    // Increase damage by 3
+                    self.stats.projectile_stats.damage += 3.0;
+                }
+            }
+            WeaponType::Tether => {
+                if self.level >= 5 {
+                    // A longer beam that holds the target tighter
+                    self.stats.tether.range *= 1.2;
+                    self.stats.tether.slow_factor = (self.stats.tether.slow_factor - 0.1).max(0.3);
+                    self.stats.tether.dps += 5.0;
+                } else {
+                    // Increase damage per second by 3
+                    self.stats.tether.dps += 3.0;
+                    // Increase range by 5%
+                    self.stats.tether.range *= 1.05;
+                }
+            }
         }
     }
 
@@ -252,3 +877,248 @@ impl Weapon {
         self.level
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_circle_spacing() {
+        let mut weapon = Weapon::new(WeaponType::EnergyBall);
+        weapon.stats.projectile_count = 8;
+        weapon.stats.full_circle = true;
+
+        let mut commands = Vec::new();
+        weapon.fire(Vec2::ZERO, Vec2::new(0.0, -1.0), 0, &mut commands);
+        assert_eq!(commands.len(), 8);
+
+        let mut angles: Vec<f32> = commands
+            .iter()
+            .map(|command| match command {
+                SpawnCommand::Projectile { vel, .. } => vel.y.atan2(vel.x),
+                _ => panic!("expected a projectile"),
+            })
+            .collect();
+        angles.sort_by(f32::total_cmp);
+
+        let step = std::f32::consts::TAU / 8.0;
+        for pair in angles.windows(2) {
+            assert!((pair[1] - pair[0] - step).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_overheated_weapon_locks_out_until_cooled() {
+        let mut weapon = Weapon::new(WeaponType::EnergyBall);
+        weapon.apply_overheat(&OverheatConfig {
+            fire_mode: FireMode::Overheat,
+            max_heat: 100.0,
+            heat_per_shot: 25.0,
+            cooldown_rate: 50.0,
+        });
+
+        // Four quick shots fill the gauge, there is no per-shot cooldown
+        let mut commands = Vec::new();
+        for _ in 0..4 {
+            assert!(weapon.can_fire());
+            weapon.fire(Vec2::ZERO, Vec2::X, 0, &mut commands);
+        }
+        assert_eq!(commands.len(), 4);
+        assert!(!weapon.can_fire());
+
+        // Still locked while partly cooled down
+        weapon.update(1.0);
+        assert!(!weapon.can_fire());
+        weapon.fire(Vec2::ZERO, Vec2::X, 0, &mut commands);
+        assert_eq!(commands.len(), 4);
+
+        // Fully cooled it fires again
+        weapon.update(1.0);
+        assert_eq!(weapon.heat_fraction(), 0.0);
+        assert!(weapon.can_fire());
+    }
+
+    #[test]
+    fn test_adaptive_weapon_spreads_out_when_surrounded() {
+        let mut weapon = Weapon::new(WeaponType::Adaptive);
+        let threshold = weapon.stats.adaptive_threshold as usize;
+        let single_damage = weapon.stats.projectile_stats.damage;
+
+        // Below the threshold it fires one full-damage shot at the facing
+        let mut commands = Vec::new();
+        weapon.fire(Vec2::ZERO, Vec2::X, threshold - 1, &mut commands);
+        assert_eq!(commands.len(), 1);
+        match &commands[0] {
+            SpawnCommand::Projectile { vel, stats, .. } => {
+                assert!(vel.normalize().abs_diff_eq(Vec2::X, 1e-4));
+                assert_eq!(stats.damage, single_damage);
+            }
+            _ => panic!("expected a projectile"),
+        }
+
+        // At the threshold it fires a weaker ring
+        weapon.cooldown_remaining = 0.0;
+        let mut commands = Vec::new();
+        weapon.fire(Vec2::ZERO, Vec2::X, threshold, &mut commands);
+        assert_eq!(commands.len(), weapon.stats.projectile_count as usize);
+        assert!(commands.iter().all(|command| match command {
+            SpawnCommand::Projectile { stats, .. } => stats.damage < single_damage,
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn test_mastery_tier_adds_damage() {
+        let mut weapon = Weapon::new(WeaponType::Pulse);
+        weapon.stats.mastery = MasteryConfig {
+            kills_per_tier: 3,
+            damage_per_tier: 2.0,
+        };
+        let damage = weapon.stats.projectile_stats.damage;
+
+        assert!(!weapon.add_kill());
+        assert!(!weapon.add_kill());
+        assert_eq!(weapon.stats.projectile_stats.damage, damage);
+
+        // The third kill crosses the threshold
+        assert!(weapon.add_kill());
+        assert_eq!(weapon.mastery_tier, 1);
+        assert_eq!(weapon.stats.projectile_stats.damage, damage + 2.0);
+
+        // Nothing more until the next threshold
+        assert!(!weapon.add_kill());
+        assert_eq!(weapon.stats.projectile_stats.damage, damage + 2.0);
+    }
+
+    #[test]
+    fn test_tether_retargets_when_its_target_despawns() {
+        let enemy_at = |id: EntityId, x: f32| Enemy::for_test(id, Vec2::new(x, 0.0));
+        let mut weapon = Weapon::new(WeaponType::Tether);
+        let mut enemies = vec![enemy_at(1, 50.0), enemy_at(2, 100.0)];
+
+        assert_eq!(
+            weapon.update_tether_target(Vec2::ZERO, &enemies, &[]),
+            Some(1)
+        );
+        // A closer enemy doesn't steal the tether
+        enemies.push(enemy_at(3, 20.0));
+        assert_eq!(
+            weapon.update_tether_target(Vec2::ZERO, &enemies, &[]),
+            Some(1)
+        );
+
+        // Once the target is gone the tether links the nearest remaining enemy
+        enemies.retain(|enemy| enemy.id != 1);
+        assert_eq!(
+            weapon.update_tether_target(Vec2::ZERO, &enemies, &[]),
+            Some(3)
+        );
+        enemies.clear();
+        assert_eq!(weapon.update_tether_target(Vec2::ZERO, &enemies, &[]), None);
+    }
+
+    #[test]
+    fn test_tether_does_not_link_through_barriers() {
+        use crate::barrier::BarrierConfig;
+        use crate::visual_config::ColorConfig;
+
+        let mut weapon = Weapon::new(WeaponType::Tether);
+        let enemies = vec![
+            Enemy::for_test(1, Vec2::new(60.0, 0.0)),
+            Enemy::for_test(2, Vec2::new(0.0, 120.0)),
+        ];
+        assert_eq!(
+            weapon.update_tether_target(Vec2::ZERO, &enemies, &[]),
+            Some(1)
+        );
+
+        // A wall between the player and the nearest enemy makes the tether switch
+        let barriers = vec![Barrier::across(
+            Vec2::new(30.0, 0.0),
+            Vec2::X,
+            &BarrierConfig::default(),
+            ColorConfig::white(),
+        )];
+        assert_eq!(
+            weapon.update_tether_target(Vec2::ZERO, &enemies, &barriers),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn test_adaptive_threshold_stays_positive_and_old_saves_load() {
+        let mut weapon = Weapon::new(WeaponType::Adaptive);
+        for _ in 0..20 {
+            weapon.level_up(&WeaponTuning::default());
+        }
+        assert_eq!(weapon.stats.adaptive_threshold, 1);
+
+        // Stats saved before the adaptive weapon existed lack its fields
+        let mut json = serde_json::to_value(WeaponStats::from(WeaponType::EnergyBall)).unwrap();
+        let fields = json.as_object_mut().unwrap();
+        fields.remove("adaptive_radius");
+        fields.remove("adaptive_threshold");
+        let stats: WeaponStats = serde_json::from_value(json).unwrap();
+        assert_eq!(stats.adaptive_radius, DEFAULT_ADAPTIVE_RADIUS);
+        assert_eq!(stats.adaptive_threshold, DEFAULT_ADAPTIVE_THRESHOLD);
+    }
+
+    #[test]
+    fn test_energy_ball_ring_follows_the_tuning_switch() {
+        let mut tuning = WeaponTuning {
+            energy_ball_full_circle: false,
+            ..WeaponTuning::default()
+        };
+        let mut weapon = Weapon::new(WeaponType::EnergyBall);
+        weapon.apply_tuning(&tuning);
+        for _ in 1..5 {
+            weapon.level_up(&tuning);
+        }
+        assert!(!weapon.stats.full_circle);
+
+        // Switching the ring back on restores it for the upgraded weapon only
+        tuning.energy_ball_full_circle = true;
+        weapon.retune(&tuning);
+        assert!(weapon.stats.full_circle);
+        let mut fresh = Weapon::new(WeaponType::EnergyBall);
+        fresh.apply_tuning(&tuning);
+        assert!(!fresh.stats.full_circle);
+    }
+
+    #[test]
+    fn test_tether_shows_its_damage_per_second() {
+        let tether = Weapon::new(WeaponType::Tether);
+        assert_eq!(
+            tether.damage_label(),
+            format!("{:.0}/s", TetherConfig::default().dps)
+        );
+        let energy_ball = Weapon::new(WeaponType::EnergyBall);
+        assert_eq!(
+            energy_ball.damage_label(),
+            format!("{:.0}", energy_ball.stats.projectile_stats.damage)
+        );
+    }
+
+    #[test]
+    fn test_weapons_from_old_saves_load() {
+        // Saves from before these fields existed lack them
+        let mut json = serde_json::to_value(Weapon::new(WeaponType::EnergyBall)).unwrap();
+        json.as_object_mut().unwrap().remove("overheated");
+        let stats = json["stats"].as_object_mut().unwrap();
+        for field in [
+            "full_circle",
+            "fire_mode",
+            "heat",
+            "max_heat",
+            "heat_per_shot",
+            "cooldown_rate",
+        ] {
+            stats.remove(field);
+        }
+        let weapon: Weapon = serde_json::from_value(json).unwrap();
+        assert!(!weapon.stats.full_circle);
+        assert_eq!(weapon.stats.fire_mode, FireMode::Cooldown);
+        assert_eq!(weapon.stats.max_heat, DEFAULT_MAX_HEAT);
+        assert!(!weapon.overheated);
+    }
+}